@@ -1,9 +1,16 @@
+pub use bool_array::*;
+pub use buffer::*;
+pub use dictionary_array::*;
 pub use dispatch::*;
 pub use primitive_array::*;
 pub use string_array::*;
 
+use crate::macros::for_all_types;
 use crate::scalar::{Scalar, ScalarRef};
 
+mod bool_array;
+mod buffer;
+mod dictionary_array;
 mod dispatch;
 mod primitive_array;
 mod string_array;
@@ -26,13 +33,18 @@ pub trait Array: Send + Sync + Sized + 'static // + TryFrom<ArrayImpl> + Into<Ar
     /// Number of items of array.
     fn len(&self) -> usize;
 
+    /// Returns a view of `len` items starting at `offset`, sharing the buffers of this array.
+    ///
+    /// Panics if the range is out of bounds.
+    fn slice(&self, offset: usize, len: usize) -> Self;
+
     /// Indicates whether this array is empty
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
     /// Returns an iterator over the array.
-    fn iter(&self) -> ArrayIterator<Self> {
+    fn iter(&self) -> ArrayIterator<'_, Self> {
         ArrayIterator {
             array: self,
             pos: 0,
@@ -40,20 +52,35 @@ pub trait Array: Send + Sync + Sized + 'static // + TryFrom<ArrayImpl> + Into<Ar
     }
 
     fn from_slice(slice: &[Option<Self::RefItem<'_>>]) -> Self {
-        let mut builder = Self::Builder::with_capacity(slice.len());
-        for item in slice {
-            builder.push(*item);
+        Self::from_items(slice.iter().copied())
+    }
+
+    /// Build an array from the items of `iter`, like [`FromIterator`] which every concrete
+    /// array implements, but usable on any `A: Array`.
+    fn from_items<'a>(iter: impl IntoIterator<Item = Option<Self::RefItem<'a>>>) -> Self {
+        let iter = iter.into_iter();
+        let mut builder = Self::Builder::with_capacity(iter.size_hint().0);
+        for item in iter {
+            builder.push(item);
         }
         builder.finish()
     }
 }
 
+/// [`ArrayBuilder`] builds an [`Array`].
 pub trait ArrayBuilder {
     type Array: Array;
 
     fn with_capacity(capacity: usize) -> Self;
     fn push(&mut self, item: Option<<Self::Array as Array>::RefItem<'_>>);
     fn finish(self) -> Self::Array;
+
+    /// Append every item of `array`.
+    fn append_array(&mut self, array: &Self::Array) {
+        for item in array.iter() {
+            self.push(item);
+        }
+    }
 }
 
 /// An iterator that iterators on any [`Array`] type.
@@ -77,11 +104,35 @@ impl<'a, A: Array> Iterator for ArrayIterator<'a, A> {
     }
 }
 
+macro_rules! impl_from_iterator {
+    ($({ $Name:ident, $Variant:ident, $Array:ident, $ArrayBuilder:ident, $Owned:ty, $Ref:ty }),*) => {
+        $(
+            impl<'a> FromIterator<Option<$Ref>> for $Array {
+                fn from_iter<I: IntoIterator<Item = Option<$Ref>>>(iter: I) -> Self {
+                    Self::from_items(iter)
+                }
+            }
+
+            impl<'a> Extend<Option<$Ref>> for $ArrayBuilder {
+                fn extend<I: IntoIterator<Item = Option<$Ref>>>(&mut self, iter: I) {
+                    for item in iter {
+                        self.push(item);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+for_all_types! { impl_from_iterator }
+
 #[cfg(test)]
 mod tests {
-    use super::primitive_array::I32Array;
-    use super::string_array::StringArray;
-    use super::{Array, ArrayBuilder, ArrayImpl};
+    use super::primitive_array::*;
+    use super::string_array::*;
+    use super::{Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, BoolArrayBuilder};
+    use crate::datatype::DataType;
+    use crate::scalar::ScalarRefImpl;
     use crate::TypeMismatch;
 
     // These are two examples of using generics over array.
@@ -103,6 +154,7 @@ mod tests {
     where
         A::RefItem<'a>: PartialEq,
     {
+        assert_eq!(array.len(), vec.len());
         for (a, b) in array.iter().zip(vec.iter()) {
             assert_eq!(&a, b);
         }
@@ -110,18 +162,148 @@ mod tests {
 
     #[test]
     fn test_build_int32_array() {
-        let data = vec![Some(1), Some(2), Some(3), None, Some(5)];
+        let data = [Some(1), Some(2), Some(3), None, Some(5)];
         let array = build_array_from_vec::<I32Array>(&data[..]);
         check_array_eq(&array, &data[..]);
     }
 
     #[test]
     fn test_build_string_array() {
-        let data = vec![Some("1"), Some("2"), Some("3"), None, Some("5"), Some("")];
+        let data = [Some("1"), Some("2"), Some("3"), None, Some("5"), Some("")];
         let array = build_array_from_vec::<StringArray>(&data[..]);
         check_array_eq(&array, &data[..]);
     }
 
+    #[test]
+    fn test_from_iterator() {
+        let array: I32Array = (0..5).map(|i| (i % 2 == 0).then_some(i)).collect();
+        check_array_eq(&array, &[Some(0), None, Some(2), None, Some(4)]);
+
+        let data = ["a", "", "c"];
+        let array: StringArray = data
+            .iter()
+            .map(|s| Some(*s).filter(|s| !s.is_empty()))
+            .collect();
+        check_array_eq(&array, &[Some("a"), None, Some("c")]);
+
+        // `Array` is in scope here, and `from_iter` still resolves to `FromIterator`.
+        let array = I32Array::from_iter([Some(1), None]);
+        check_array_eq(&array, &[Some(1), None]);
+        let array = StringArray::from_iter([Some("a"), None]);
+        check_array_eq(&array, &[Some("a"), None]);
+    }
+
+    /// Keep every other item, on any array type.
+    fn every_other<A: Array>(array: &A) -> A {
+        A::from_items(array.iter().step_by(2))
+    }
+
+    #[test]
+    fn test_generic_from_items() {
+        let array = every_other(&StringArray::from_slice(&[Some("a"), Some("b"), None]));
+        check_array_eq(&array, &[Some("a"), None]);
+        let array = every_other(&I32Array::from_slice(&[None, Some(1), Some(2)]));
+        check_array_eq(&array, &[None, Some(2)]);
+
+        // Builders implement `Extend`, so they work where std expects it.
+        fn fill(builder: &mut impl Extend<Option<i32>>) {
+            builder.extend((0..2).map(Some));
+            builder.extend([None]);
+        }
+        let mut builder = I32ArrayBuilder::with_capacity(0);
+        fill(&mut builder);
+        check_array_eq(&builder.finish(), &[Some(0), Some(1), None]);
+    }
+
+    #[test]
+    fn test_extend_and_append_array() {
+        let array = StringArray::from_slice(&[Some("a"), None, Some("c"), Some("d")]);
+        let mut builder = <StringArray as Array>::Builder::with_capacity(0);
+        builder.extend([Some("x"), None]);
+        builder.append_array(&array.slice(1, 3));
+        check_array_eq(
+            &builder.finish(),
+            &[Some("x"), None, None, Some("c"), Some("d")],
+        );
+
+        let array: ArrayImpl = I32Array::from_slice(&[Some(1), None]).into();
+        let mut builder = ArrayBuilderImpl::with_capacity(DataType::Int32, 0);
+        builder.append_array(&array).unwrap();
+        builder.extend([None, Some(ScalarRefImpl::I32(3))]).unwrap();
+        builder.append_array(&array.slice(1, 1)).unwrap();
+        assert_eq!(
+            builder.append_array(&StringArray::from_slice(&[None]).into()),
+            Err(TypeMismatch {
+                expected: DataType::Int32,
+                actual: DataType::String,
+            })
+        );
+        assert_eq!(
+            builder.extend([Some(ScalarRefImpl::I32(4)), Some(ScalarRefImpl::Bool(true))]),
+            Err(TypeMismatch {
+                expected: DataType::Int32,
+                actual: DataType::Bool,
+            })
+        );
+        let built: I32Array = builder.finish().try_into().unwrap();
+        check_array_eq(&built, &[Some(1), None, None, Some(3), None, Some(4)]);
+    }
+
+    #[test]
+    fn test_try_push() {
+        let mut builder = ArrayBuilderImpl::new(DataType::String);
+        assert_eq!(builder.data_type(), DataType::String);
+        builder.try_push(Some(ScalarRefImpl::String("a"))).unwrap();
+        builder.try_push(None).unwrap();
+        assert_eq!(
+            builder.try_push(Some(ScalarRefImpl::I64(1))),
+            Err(TypeMismatch {
+                expected: DataType::String,
+                actual: DataType::Int64,
+            })
+        );
+        let built: StringArray = builder.finish().try_into().unwrap();
+        check_array_eq(&built, &[Some("a"), None]);
+    }
+
+    #[test]
+    #[should_panic(expected = "type mismatch: expected Bool, got Int32")]
+    fn test_push_type_mismatch() {
+        ArrayBuilderImpl::new(DataType::Bool).push(Some(ScalarRefImpl::I32(1)));
+    }
+
+    #[test]
+    fn test_builder_conversions() {
+        let mut builder = StringArrayBuilder::with_capacity(1);
+        builder.push(Some("a"));
+        let mut builder: ArrayBuilderImpl = builder.into();
+        assert_eq!(builder.data_type(), DataType::String);
+        builder.push(None);
+        let mut builder: StringArrayBuilder = builder.try_into().unwrap();
+        builder.push(Some("c"));
+        check_array_eq(&builder.finish(), &[Some("a"), None, Some("c")]);
+
+        let builder: ArrayBuilderImpl = I32ArrayBuilder::with_capacity(0).into();
+        assert_eq!(
+            StringArrayBuilder::try_from(builder).err(),
+            Some(TypeMismatch {
+                expected: DataType::String,
+                actual: DataType::Int32,
+            })
+        );
+        for data_type in [DataType::Int64, DataType::Float64, DataType::Bool] {
+            let builder = ArrayBuilderImpl::new(data_type);
+            let builder = match data_type {
+                DataType::Int64 => {
+                    ArrayBuilderImpl::from(I64ArrayBuilder::try_from(builder).unwrap())
+                }
+                DataType::Float64 => F64ArrayBuilder::try_from(builder).unwrap().into(),
+                _ => BoolArrayBuilder::try_from(builder).unwrap().into(),
+            };
+            assert_eq!(builder.finish().data_type(), data_type);
+        }
+    }
+
     fn add_i32(a: i32, b: i32) -> i32 {
         a + b
     }
@@ -0,0 +1,210 @@
+use bitvec::field::BitField;
+use bitvec::slice::BitSlice;
+use bitvec::vec::BitVec;
+
+use crate::array::{Array, ArrayBuilder, Bitmap};
+
+/// An array of booleans, with both values and validity packed into bitmaps.
+#[derive(Clone)]
+pub struct BoolArray {
+    /// The packed values of this array. Null slots hold `false`.
+    data: Bitmap,
+    /// The null bitmap of this array.
+    bitmap: Bitmap,
+    /// Position of the first item of this array in `data` and `bitmap`.
+    offset: usize,
+    /// Number of items of this array.
+    len: usize,
+}
+
+impl BoolArray {
+    /// Create an array of `len` items from packed values and null bitmap words, least
+    /// significant bit first. Values of null slots are cleared.
+    ///
+    /// Panics if there are fewer than `len` bits in either `values` or `validity`.
+    pub fn from_words(values: Vec<usize>, validity: Vec<usize>, len: usize) -> Self {
+        let words = len.div_ceil(usize::BITS as usize);
+        assert!(
+            values.len() >= words && validity.len() >= words,
+            "not enough words for {len} items"
+        );
+        let values = values.iter().zip(&validity).map(|(v, m)| v & m).collect();
+        let mut data = BitVec::from_vec(values);
+        let mut bitmap = BitVec::from_vec(validity);
+        data.truncate(len);
+        bitmap.truncate(len);
+        Self {
+            data: data.into(),
+            bitmap: bitmap.into(),
+            offset: 0,
+            len,
+        }
+    }
+
+    fn get(&self, idx: usize) -> Option<bool> {
+        assert!(idx < self.len, "index out of bounds");
+        let idx = self.offset + idx;
+        if self.bitmap[idx] {
+            Some(self.data[idx])
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn slice(&self, offset: usize, len: usize) -> Self {
+        assert!(
+            offset.checked_add(len).is_some_and(|end| end <= self.len),
+            "slice out of bounds"
+        );
+        Self {
+            data: self.data.clone(),
+            bitmap: self.bitmap.clone(),
+            offset: self.offset + offset,
+            len,
+        }
+    }
+
+    /// The packed values of this array. The value of a null slot is `false`.
+    pub fn values(&self) -> &BitSlice {
+        &self.data[self.offset..self.offset + self.len]
+    }
+
+    /// The null bitmap of this array, where a set bit marks a non-null slot.
+    pub fn validity(&self) -> &BitSlice {
+        &self.bitmap[self.offset..self.offset + self.len]
+    }
+
+    /// The packed values of this array as machine words, least significant bit first.
+    ///
+    /// Bits past `len()` in the last word are zero.
+    pub fn value_words(&self) -> impl Iterator<Item = usize> + '_ {
+        to_words(self.values())
+    }
+
+    /// The null bitmap of this array as machine words, least significant bit first.
+    ///
+    /// Bits past `len()` in the last word are zero.
+    pub fn validity_words(&self) -> impl Iterator<Item = usize> + '_ {
+        to_words(self.validity())
+    }
+}
+
+/// Load a bitmap one machine word at a time, no matter where it starts in its buffer.
+fn to_words(bits: &BitSlice) -> impl Iterator<Item = usize> + '_ {
+    bits.chunks(usize::BITS as usize)
+        .map(|chunk| chunk.load_le::<usize>())
+}
+
+impl Array for BoolArray {
+    type OwnedItem = bool;
+    type RefItem<'a> = bool;
+    type Builder = BoolArrayBuilder;
+    fn get(&self, idx: usize) -> Option<Self::RefItem<'_>> {
+        self.get(idx)
+    }
+    fn len(&self) -> usize {
+        self.len()
+    }
+    fn slice(&self, offset: usize, len: usize) -> Self {
+        self.slice(offset, len)
+    }
+}
+
+/// Builds a [`BoolArray`].
+pub struct BoolArrayBuilder {
+    /// The packed values of this array. Null slots hold `false`.
+    data: BitVec,
+    /// The null bitmap of this array.
+    bitmap: BitVec,
+}
+
+impl ArrayBuilder for BoolArrayBuilder {
+    type Array = BoolArray;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: BitVec::with_capacity(capacity),
+            bitmap: BitVec::with_capacity(capacity),
+        }
+    }
+
+    fn push(&mut self, item: Option<<Self::Array as Array>::RefItem<'_>>) {
+        self.bitmap.push(item.is_some());
+        self.data.push(item.unwrap_or_default());
+    }
+
+    fn finish(self) -> Self::Array {
+        BoolArray {
+            len: self.bitmap.len(),
+            data: self.data.into(),
+            bitmap: self.bitmap.into(),
+            offset: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::test_util::*;
+
+    proptest! {
+        #[test]
+        fn test_bool_array_round_trip(data in prop::collection::vec(any::<Option<bool>>(), 0..200)) {
+            let array = BoolArray::from_slice(&data);
+            check_array_eq(&array, &data);
+        }
+    }
+
+    fn check_words(array: &BoolArray, data: &[Option<bool>]) {
+        let values: Vec<_> = array.value_words().collect();
+        let validity: Vec<_> = array.validity_words().collect();
+        assert_eq!(values.len(), data.len().div_ceil(usize::BITS as usize));
+        assert_eq!(validity.len(), values.len());
+        for (i, item) in data.iter().enumerate() {
+            let (word, bit) = (i / usize::BITS as usize, i % usize::BITS as usize);
+            assert_eq!(validity[word] >> bit & 1 == 1, item.is_some());
+            assert_eq!(values[word] >> bit & 1 == 1, item.unwrap_or(false));
+        }
+        if let (Some(last), rest @ 1..) = (values.last(), data.len() % usize::BITS as usize) {
+            assert_eq!(last >> rest, 0);
+        }
+    }
+
+    #[test]
+    fn test_bool_array_words() {
+        let data: Vec<_> = (0..200)
+            .map(|i| if i % 3 == 0 { None } else { Some(i % 2 == 0) })
+            .collect();
+        let array = BoolArray::from_slice(&data);
+        check_words(&array, &data);
+        check_words(&array.slice(5, 150), &data[5..155]);
+        check_array_eq(&array.slice(70, 10), &data[70..80]);
+    }
+
+    #[test]
+    fn test_bool_array_from_words() {
+        let data: Vec<_> = (0..100)
+            .map(|i| if i % 3 == 0 { None } else { Some(i % 2 == 0) })
+            .collect();
+        let array = BoolArray::from_slice(&data).slice(3, 90);
+        let from_words = BoolArray::from_words(
+            array.value_words().collect(),
+            array.validity_words().collect(),
+            array.len(),
+        );
+        check_array_eq(&from_words, &data[3..93]);
+        check_words(&from_words, &data[3..93]);
+
+        // Values under nulls are dropped.
+        let array = BoolArray::from_words(vec![0b111], vec![0b101], 3);
+        check_array_eq(&array, &[Some(true), None, Some(true)]);
+        assert_eq!(array.value_words().collect::<Vec<_>>(), [0b101]);
+    }
+}
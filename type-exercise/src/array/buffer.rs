@@ -0,0 +1,80 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use bitvec::boxed::BitBox;
+use bitvec::slice::BitSlice;
+use bitvec::vec::BitVec;
+
+/// An immutable buffer of items, shared by all slices of an array.
+pub struct Buffer<T> {
+    data: Arc<[T]>,
+}
+
+impl<T> Clone for Buffer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// Freeze a vector into a buffer, dropping its spare capacity.
+impl<T> From<Vec<T>> for Buffer<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self { data: data.into() }
+    }
+}
+
+impl<T> Deref for Buffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.data
+    }
+}
+
+/// An immutable bitmap, shared by all slices of an array.
+#[derive(Clone)]
+pub struct Bitmap {
+    bits: Arc<BitBox>,
+}
+
+/// Freeze a bit vector into a bitmap, dropping its spare capacity.
+impl From<BitVec> for Bitmap {
+    fn from(bits: BitVec) -> Self {
+        Self {
+            bits: Arc::new(bits.into_boxed_bitslice()),
+        }
+    }
+}
+
+impl Deref for Bitmap {
+    type Target = BitSlice;
+
+    fn deref(&self) -> &BitSlice {
+        &self.bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_freeze() {
+        let mut data = Vec::with_capacity(16);
+        data.extend([1, 2, 3]);
+        let buffer = Buffer::from(data);
+        let shared = buffer.clone();
+        assert_eq!(&*shared, [1, 2, 3]);
+        assert!(std::ptr::eq(&buffer[0], &shared[0]));
+
+        let mut bits = BitVec::with_capacity(128);
+        bits.extend([true, false, true]);
+        let bitmap = Bitmap::from(bits);
+        assert_eq!(&*bitmap, bits![1, 0, 1]);
+        assert_eq!(bitmap.clone().len(), 3);
+    }
+}
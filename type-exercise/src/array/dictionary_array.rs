@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+
+use crate::array::{
+    Array, ArrayBuilder, ArrayImpl, I32Array, I32ArrayBuilder, StringArray, StringArrayBuilder,
+};
+
+/// A string array stored as keys into a dictionary of values, for columns that repeat a few
+/// distinct strings. A null key is a null item.
+///
+/// It is not an [`Array`] on its own, as `&str` already belongs to [`StringArray`]. It is
+/// used through [`ArrayImpl::Dictionary`], which reads like a `String` array.
+#[derive(Clone)]
+pub struct DictionaryArray {
+    keys: I32Array,
+    dictionary: StringArray,
+}
+
+/// Error of creating a [`DictionaryArray`] whose keys do not all index into its dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("dictionary key {key} is out of bounds of {len} dictionary entries")]
+pub struct InvalidDictionaryKey {
+    pub key: i32,
+    pub len: usize,
+}
+
+impl DictionaryArray {
+    /// Create an array from `keys` indexing into `dictionary`, checking that every key is in
+    /// bounds.
+    pub fn try_new(keys: I32Array, dictionary: StringArray) -> Result<Self, InvalidDictionaryKey> {
+        let len = dictionary.len();
+        if let Some(key) = keys
+            .iter()
+            .flatten()
+            .find(|key| usize::try_from(*key).map_or(true, |key| key >= len))
+        {
+            return Err(InvalidDictionaryKey { key, len });
+        }
+        Ok(Self::new_unchecked(keys, dictionary))
+    }
+
+    /// Create an array from `keys` indexing into `dictionary`.
+    ///
+    /// Panics if a key is out of bounds of `dictionary`.
+    pub fn new(keys: I32Array, dictionary: StringArray) -> Self {
+        match Self::try_new(keys, dictionary) {
+            Ok(array) => array,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    fn new_unchecked(keys: I32Array, dictionary: StringArray) -> Self {
+        Self { keys, dictionary }
+    }
+
+    pub fn keys(&self) -> &I32Array {
+        &self.keys
+    }
+
+    pub fn dictionary(&self) -> &StringArray {
+        &self.dictionary
+    }
+
+    /// Retrieve the value at `idx`.
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        let key = self.keys.get(idx)?;
+        self.dictionary.get(key as usize)
+    }
+
+    /// Number of items of array.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a view of `len` items starting at `offset`, sharing the keys and the dictionary.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(&self, offset: usize, len: usize) -> Self {
+        Self::new_unchecked(self.keys.slice(offset, len), self.dictionary.clone())
+    }
+
+    /// Returns an iterator over the values of the array.
+    pub fn iter(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        (0..self.len()).map(|idx| self.get(idx))
+    }
+
+    /// Expand the keys into a plain [`StringArray`].
+    pub fn decode(&self) -> StringArray {
+        self.iter().collect()
+    }
+
+    /// Get the items at `indices`, where a null index gives a null item. The dictionary is
+    /// shared.
+    ///
+    /// Panics if an index is out of bounds.
+    pub fn take(&self, indices: &I32Array) -> Self {
+        let keys = indices
+            .iter()
+            .map(|idx| idx.and_then(|idx| self.keys.get(idx as usize)))
+            .collect();
+        Self::new_unchecked(keys, self.dictionary.clone())
+    }
+
+    /// Evaluate `f` once per distinct value rather than once per row, then map its results
+    /// back through the keys.
+    ///
+    /// `f` gets the dictionary entries some key refers to, followed by a null entry if any key
+    /// is null, and must return one result per entry. A `String` result is returned as a new
+    /// dictionary, other results are expanded to one item per row.
+    pub fn map_entries<E>(
+        &self,
+        f: impl FnOnce(&ArrayImpl) -> Result<ArrayImpl, E>,
+    ) -> Result<ArrayImpl, E> {
+        let mut used = vec![false; self.dictionary.len()];
+        let mut has_null = false;
+        for key in self.keys.iter() {
+            match key {
+                Some(key) => used[key as usize] = true,
+                None => has_null = true,
+            }
+        }
+
+        // If every entry is used and no key is null, the dictionary and keys are used as is.
+        // Otherwise the used entries are compacted and the keys remapped to them, with null keys
+        // pointing at the extra null entry.
+        let (entries, keys) = if !has_null && used.iter().all(|used| *used) {
+            (self.dictionary.clone(), self.keys.clone())
+        } else {
+            let mut positions = vec![0; used.len()];
+            let mut entries = StringArrayBuilder::with_capacity(used.len() + 1);
+            let mut count = 0;
+            for (idx, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+                positions[idx] = count;
+                entries.push(self.dictionary.get(idx));
+                count += 1;
+            }
+            if has_null {
+                entries.push(None);
+            }
+            let keys = self
+                .keys
+                .iter()
+                .map(|key| Some(key.map_or(count, |key| positions[key as usize])))
+                .collect();
+            (entries.finish(), keys)
+        };
+
+        let len = entries.len();
+        let results = f(&entries.into())?;
+        assert_eq!(
+            results.len(),
+            len,
+            "expected one result per dictionary entry"
+        );
+        Ok(match results {
+            ArrayImpl::String(results) => Self::new_unchecked(keys, results).into(),
+            results => results.take(&keys),
+        })
+    }
+}
+
+/// Dictionary-encode the strings, keeping the values in the order they are first seen.
+///
+/// Panics if there are more distinct strings than `i32` keys can index.
+impl<'a> FromIterator<Option<&'a str>> for DictionaryArray {
+    fn from_iter<I: IntoIterator<Item = Option<&'a str>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut keys = I32ArrayBuilder::with_capacity(iter.size_hint().0);
+        let mut dictionary = StringArrayBuilder::with_capacity(0);
+        let mut seen = HashMap::new();
+        for item in iter {
+            keys.push(item.map(|item| {
+                let next =
+                    i32::try_from(seen.len()).expect("too many distinct values for i32 keys");
+                let key = *seen.entry(item).or_insert(next);
+                if key == next {
+                    dictionary.push(Some(item));
+                }
+                key
+            }));
+        }
+        Self::new_unchecked(keys.finish(), dictionary.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::scalar::ScalarRefImpl;
+    use crate::test_util::*;
+    use crate::{EvalError, TypeMismatch};
+
+    const DATA: [Option<&str>; 6] = [Some("de"), Some("fr"), None, Some("de"), Some("it"), None];
+
+    #[test]
+    fn test_dictionary_array() {
+        let array: DictionaryArray = DATA.into_iter().collect();
+        check_array_eq(array.dictionary(), &[Some("de"), Some("fr"), Some("it")]);
+        check_array_eq(
+            array.keys(),
+            &[Some(0), Some(1), None, Some(0), Some(2), None],
+        );
+        check_array_eq(&array.decode(), &DATA);
+        check_array_eq(&array.slice(1, 3).decode(), &DATA[1..4]);
+
+        let array: ArrayImpl = array.into();
+        assert_eq!(array.data_type(), DataType::String);
+        assert_eq!(array.len(), 6);
+        assert_eq!(array.get(3), Some(ScalarRefImpl::String("de")));
+        assert_eq!(array.slice(2, 1).get(0), None);
+
+        // Converting to the concrete type needs the dictionary to be decoded first.
+        let mismatch = <&StringArray>::try_from(&array).err().unwrap();
+        assert_eq!(
+            mismatch.to_string(),
+            "type mismatch: expected String, got dictionary-encoded String"
+        );
+        assert!(StringArray::try_from(array.clone()).is_err());
+        check_array_eq(<&StringArray>::try_from(&*array.decode()).unwrap(), &DATA);
+        assert_eq!(
+            <&I32Array>::try_from(&array).err(),
+            Some(TypeMismatch {
+                expected: DataType::Int32,
+                actual: DataType::String,
+            })
+        );
+
+        let mut builder = ArrayBuilderImpl::new(DataType::String);
+        builder.append_array(&array).unwrap();
+        let built: StringArray = builder.finish().try_into().unwrap();
+        check_array_eq(&built, &DATA);
+    }
+
+    #[test]
+    fn test_key_out_of_bounds() {
+        let dictionary = StringArray::from_slice(&[Some("a"), Some("b")]);
+        for key in [2, -1] {
+            let keys = I32Array::from_slice(&[Some(0), None, Some(key)]);
+            assert_eq!(
+                DictionaryArray::try_new(keys, dictionary.clone()).err(),
+                Some(InvalidDictionaryKey { key, len: 2 })
+            );
+        }
+        let keys = I32Array::from_slice(&[Some(1), None, Some(0)]);
+        let array = DictionaryArray::try_new(keys, dictionary).unwrap();
+        check_array_eq(&array.decode(), &[Some("b"), None, Some("a")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "dictionary key 1 is out of bounds of 1 dictionary entries")]
+    fn test_new_out_of_bounds() {
+        DictionaryArray::new(
+            I32Array::from_slice(&[Some(0), Some(1)]),
+            StringArray::from_slice(&[Some("a")]),
+        );
+    }
+
+    #[test]
+    fn test_eval_per_entry() {
+        let array: ArrayImpl = DATA.into_iter().collect::<DictionaryArray>().into();
+        let decoded = array.decode();
+
+        let expr = BinaryExpression::<StringArray, StringArray, BoolArray, _>::new(str_contains);
+        let result = expr
+            .eval_array_scalar(&array, ScalarRefImpl::String("e"))
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(false), None, Some(true), Some(false), None],
+        );
+        let result = expr
+            .eval_scalar_array(ScalarRefImpl::String("fr"), &array)
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[
+                Some(false),
+                Some(true),
+                None,
+                Some(false),
+                Some(false),
+                None,
+            ],
+        );
+
+        // Null keys go through the function too, so null-aware functions see them.
+        let registry = FunctionRegistry::with_builtins();
+        let is_null = registry.build("is_null", &[DataType::String]).unwrap();
+        let result = is_null.eval_expr(&[&array]).unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &DATA.map(|item| Some(item.is_none())),
+        );
+
+        // String results stay dictionary-encoded.
+        let upper = registry.build("upper", &[DataType::String]).unwrap();
+        let result = upper.eval_expr(&[&array.slice(1, 4)]).unwrap();
+        let ArrayImpl::Dictionary(upper) = &result else {
+            panic!("expected a dictionary");
+        };
+        check_array_eq(
+            upper.dictionary(),
+            &[Some("DE"), Some("FR"), Some("IT"), None],
+        );
+        check_array_eq(&upper.decode(), &[Some("FR"), None, Some("DE"), Some("IT")]);
+
+        // Kernels without a per-entry path decode the dictionary first.
+        let other: ArrayImpl = StringArray::from_slice(&[Some("fr"); 6]).into();
+        let lt = registry.build("cmp_lt", &[DataType::String; 2]).unwrap();
+        let result = lt.eval_expr(&[&array, &other]).unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(false), None, Some(true), Some(false), None],
+        );
+        let result = crate::eval_binary(ExpressionFunc::CmpEq, &array, &decoded).unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(true), None, Some(true), Some(true), None],
+        );
+    }
+
+    #[test]
+    fn test_map_entries_only_used() {
+        let keys = I32Array::from_slice(&[Some(0), Some(2), Some(0), Some(1), Some(2)]);
+        let dictionary = StringArray::from_slice(&[Some("1"), Some("x"), Some("2")]);
+        let array = DictionaryArray::new(keys, dictionary);
+
+        // The entry "x" is out of the slice, so it is neither parsed nor an error.
+        let parse = UnaryExpression::<StringArray, I32Array, _, _>::new_checked(|s: &str| {
+            s.parse::<i32>()
+                .map_err(|e| EvalError::InvalidArgument(e.to_string()))
+        });
+        let mut entries = 0;
+        let result = array
+            .slice(0, 3)
+            .map_entries(|i| {
+                entries = i.len();
+                parse.eval(i)
+            })
+            .unwrap();
+        assert_eq!(entries, 2);
+        check_array_eq::<I32Array>((&result).try_into().unwrap(), &[Some(1), Some(2), Some(1)]);
+        assert!(parse.eval(&array.into()).is_err());
+
+        let array = I32Array::from_slice(&[Some(5), None, Some(7)]).into();
+        let result = ArrayImpl::take(&array, &I32Array::from_slice(&[Some(2), None, Some(0)]));
+        check_array_eq::<I32Array>((&result).try_into().unwrap(), &[Some(7), None, Some(5)]);
+    }
+}
@@ -1,15 +1,22 @@
+use std::borrow::Cow;
+
 use crate::array::*;
+use crate::datatype::DataType;
 use crate::macros::for_all_types;
 use crate::scalar::*;
 use crate::TypeMismatch;
 
 macro_rules! array_dispatch {
     ($({ $Name:ident, $Variant:ident, $Array:ty, $ArrayBuilder:ty, $Owned:ty, $Ref:ty }),*) => {
-        /// All variants of [`Array`].
+        /// All variants of [`Array`], plus dictionary-encoded strings. Cloning is cheap, as the
+        /// buffers are shared.
+        #[derive(Clone)]
         pub enum ArrayImpl {
             $(
                 $Variant($Array),
             )*
+            /// Strings stored as keys into a dictionary. Reads like a `String` array.
+            Dictionary(DictionaryArray),
         }
 
         /// All variants of [`ArrayBuilder`].
@@ -27,6 +34,7 @@ macro_rules! array_dispatch {
                     $(
                         Self::$Variant(this) => this.get(idx).map(ScalarRefImpl::$Variant),
                     )*
+                    Self::Dictionary(this) => this.get(idx).map(ScalarRefImpl::String),
                 }
             }
 
@@ -36,6 +44,7 @@ macro_rules! array_dispatch {
                     $(
                         Self::$Variant(this) => this.len(),
                     )*
+                    Self::Dictionary(this) => this.len(),
                 }
             }
 
@@ -43,19 +52,134 @@ macro_rules! array_dispatch {
             pub fn is_empty(&self) -> bool {
                 self.len() == 0
             }
+
+            /// Get a view of `len` items starting at `offset`, sharing the buffers of this array.
+            pub fn slice(&self, offset: usize, len: usize) -> Self {
+                match self {
+                    $(
+                        Self::$Variant(this) => Self::$Variant(this.slice(offset, len)),
+                    )*
+                    Self::Dictionary(this) => Self::Dictionary(this.slice(offset, len)),
+                }
+            }
+
+            /// Get the items at `indices`, where a null index gives a null item.
+            ///
+            /// Panics if an index is out of bounds.
+            pub fn take(&self, indices: &I32Array) -> Self {
+                match self {
+                    $(
+                        Self::$Variant(this) => Self::$Variant(
+                            indices
+                                .iter()
+                                .map(|idx| idx.and_then(|idx| this.get(idx as usize)))
+                                .collect(),
+                        ),
+                    )*
+                    Self::Dictionary(this) => Self::Dictionary(this.take(indices)),
+                }
+            }
+
+            /// Get the data type of the array.
+            pub fn data_type(&self) -> DataType {
+                match self {
+                    $(
+                        Self::$Variant(_) => DataType::$Name,
+                    )*
+                    Self::Dictionary(_) => DataType::String,
+                }
+            }
+
+            /// Get this array with any dictionary encoding expanded, so it can be converted to
+            /// its concrete [`Array`] type. Other arrays are borrowed.
+            pub fn decode(&self) -> Cow<'_, Self> {
+                match self {
+                    Self::Dictionary(this) => Cow::Owned(Self::String(this.decode())),
+                    other => Cow::Borrowed(other),
+                }
+            }
         }
 
         // Dispatch methods for ArrayBuilderImpl.
         impl ArrayBuilderImpl {
+            /// Create a builder for arrays of `data_type`.
+            pub fn new(data_type: DataType) -> Self {
+                Self::with_capacity(data_type, 0)
+            }
+
+            /// Create a builder for arrays of `data_type` with the given capacity.
+            pub fn with_capacity(data_type: DataType, capacity: usize) -> Self {
+                match data_type {
+                    $(
+                        DataType::$Name => Self::$Variant(<$ArrayBuilder>::with_capacity(capacity)),
+                    )*
+                }
+            }
+
+            /// Get the data type of the arrays built by this builder.
+            pub fn data_type(&self) -> DataType {
+                match self {
+                    $(
+                        Self::$Variant(_) => DataType::$Name,
+                    )*
+                }
+            }
+
             /// Append a value to the array.
+            ///
+            /// Panics if the value is not of the data type of this builder.
             pub fn push(&mut self, value: Option<ScalarRefImpl<'_>>) {
+                if let Err(e) = self.try_push(value) {
+                    panic!("{e}");
+                }
+            }
+
+            /// Append a value to the array, or fail if it is not of the data type of this
+            /// builder.
+            pub fn try_push(&mut self, value: Option<ScalarRefImpl<'_>>) -> Result<(), TypeMismatch> {
                 match (self, value) {
                     $(
                         (Self::$Variant(this), Some(ScalarRefImpl::$Variant(v))) => this.push(Some(v)),
                         (Self::$Variant(this), None) => this.push(None),
                     )*
-                    _ => { panic!("Unexpected type") }
+                    (this, Some(value)) => {
+                        return Err(TypeMismatch {
+                            expected: this.data_type(),
+                            actual: value.data_type(),
+                        })
+                    }
                 }
+                Ok(())
+            }
+
+            /// Append every value of `iter`, or fail at the first one that is not of the data
+            /// type of this builder. The values before it are kept.
+            pub fn extend<'a>(
+                &mut self,
+                iter: impl IntoIterator<Item = Option<ScalarRefImpl<'a>>>,
+            ) -> Result<(), TypeMismatch> {
+                for value in iter {
+                    self.try_push(value)?;
+                }
+                Ok(())
+            }
+
+            /// Append every value of `array`, or fail if it is not of the data type of this
+            /// builder.
+            pub fn append_array(&mut self, array: &ArrayImpl) -> Result<(), TypeMismatch> {
+                match (self, array) {
+                    $(
+                        (Self::$Variant(this), ArrayImpl::$Variant(array)) => this.append_array(array),
+                    )*
+                    (Self::String(this), ArrayImpl::Dictionary(array)) => this.extend(array.iter()),
+                    (this, array) => {
+                        return Err(TypeMismatch {
+                            expected: this.data_type(),
+                            actual: array.data_type(),
+                        })
+                    }
+                }
+                Ok(())
             }
 
             /// Finish building the array.
@@ -68,14 +192,18 @@ macro_rules! array_dispatch {
             }
         }
 
-        // Conversion between ArrayImpl and Array.
+        // Conversion between ArrayImpl and Array. Dictionary-encoded strings have to be
+        // decoded first.
         $(
             impl std::convert::TryFrom<ArrayImpl> for $Array {
                 type Error = TypeMismatch;
                 fn try_from(array: ArrayImpl) -> Result<Self, Self::Error> {
                     match array {
                         ArrayImpl::$Variant(this) => Ok(this),
-                        _ => Err(TypeMismatch),
+                        other => Err(TypeMismatch {
+                            expected: DataType::$Name,
+                            actual: other.data_type(),
+                        }),
                     }
                 }
             }
@@ -85,7 +213,10 @@ macro_rules! array_dispatch {
                 fn try_from(array: &'a ArrayImpl) -> Result<Self, Self::Error> {
                     match array {
                         ArrayImpl::$Variant(this) => Ok(this),
-                        _ => Err(TypeMismatch),
+                        other => Err(TypeMismatch {
+                            expected: DataType::$Name,
+                            actual: other.data_type(),
+                        }),
                     }
                 }
             }
@@ -97,21 +228,30 @@ macro_rules! array_dispatch {
             }
         )*
 
+        impl From<DictionaryArray> for ArrayImpl {
+            fn from(array: DictionaryArray) -> Self {
+                Self::Dictionary(array)
+            }
+        }
+
         // Conversion between ArrayBuilderImpl and ArrayBuilder.
         $(
             impl std::convert::TryFrom<ArrayBuilderImpl> for $ArrayBuilder {
                 type Error = TypeMismatch;
-                fn try_from(array: ArrayBuilderImpl) -> Result<Self, Self::Error> {
-                    match array {
+                fn try_from(builder: ArrayBuilderImpl) -> Result<Self, Self::Error> {
+                    match builder {
                         ArrayBuilderImpl::$Variant(this) => Ok(this),
-                        _ => Err(TypeMismatch),
+                        other => Err(TypeMismatch {
+                            expected: DataType::$Name,
+                            actual: other.data_type(),
+                        }),
                     }
                 }
             }
 
             impl From<$ArrayBuilder> for ArrayBuilderImpl {
-                fn from(array: $Array) -> Self {
-                    Self::$Variant(array)
+                fn from(builder: $ArrayBuilder) -> Self {
+                    Self::$Variant(builder)
                 }
             }
         )*
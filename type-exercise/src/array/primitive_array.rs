@@ -1,14 +1,19 @@
 use bitvec::vec::BitVec;
 
-use crate::array::{Array, ArrayBuilder};
+use crate::array::{Array, ArrayBuilder, Bitmap, Buffer};
 use crate::macros::for_all_primitive_types;
 use crate::scalar::{PrimitiveType, Scalar, ScalarRef};
 
+#[derive(Clone)]
 pub struct PrimitiveArray<T> {
-    /// The actual data of this array.
-    data: Vec<T>,
+    /// The actual data of this array. Null slots hold `T::default()`.
+    data: Buffer<T>,
     /// The null bitmap of this array.
-    bitmap: BitVec,
+    bitmap: Bitmap,
+    /// Position of the first item of this array in `data` and `bitmap`.
+    offset: usize,
+    /// Number of items of this array.
+    len: usize,
 }
 
 impl<T> Array for PrimitiveArray<T>
@@ -26,6 +31,9 @@ where
     fn len(&self) -> usize {
         self.len()
     }
+    fn slice(&self, offset: usize, len: usize) -> Self {
+        self.slice(offset, len)
+    }
 }
 
 impl<T> PrimitiveArray<T>
@@ -33,6 +41,8 @@ where
     T: PrimitiveType,
 {
     fn get(&self, idx: usize) -> Option<T> {
+        assert!(idx < self.len, "index out of bounds");
+        let idx = self.offset + idx;
         if self.bitmap[idx] {
             Some(self.data[idx])
         } else {
@@ -41,19 +51,38 @@ where
     }
 
     fn len(&self) -> usize {
-        self.data.len()
+        self.len
+    }
+
+    fn slice(&self, offset: usize, len: usize) -> Self {
+        assert!(
+            offset.checked_add(len).is_some_and(|end| end <= self.len),
+            "slice out of bounds"
+        );
+        Self {
+            data: self.data.clone(),
+            bitmap: self.bitmap.clone(),
+            offset: self.offset + offset,
+            len,
+        }
     }
 }
 
-type PrimitiveArrayBuilder<T> = PrimitiveArray<T>;
+/// Builds a [`PrimitiveArray`].
+pub struct PrimitiveArrayBuilder<T> {
+    /// The actual data of this array. Null slots hold `T::default()`.
+    data: Vec<T>,
+    /// The null bitmap of this array.
+    bitmap: BitVec,
+}
 
 impl<T> ArrayBuilder for PrimitiveArrayBuilder<T>
 where
     T: PrimitiveType,
-    T: for<'a> Scalar<ArrayType = Self, RefType<'a> = T>,
-    T: for<'a> ScalarRef<'a, ArrayType = Self, ScalarType = T>,
+    T: for<'a> Scalar<ArrayType = PrimitiveArray<T>, RefType<'a> = T>,
+    T: for<'a> ScalarRef<'a, ArrayType = PrimitiveArray<T>, ScalarType = T>,
 {
-    type Array = Self;
+    type Array = PrimitiveArray<T>;
 
     fn with_capacity(capacity: usize) -> Self {
         Self {
@@ -64,13 +93,16 @@ where
 
     fn push(&mut self, item: Option<<Self::Array as Array>::RefItem<'_>>) {
         self.bitmap.push(item.is_some());
-        if let Some(value) = item {
-            self.data.push(value);
-        }
+        self.data.push(item.unwrap_or_default());
     }
 
     fn finish(self) -> Self::Array {
-        self
+        PrimitiveArray {
+            len: self.data.len(),
+            data: self.data.into(),
+            bitmap: self.bitmap.into(),
+            offset: 0,
+        }
     }
 }
 
@@ -84,3 +116,69 @@ macro_rules! define_primitive_array {
 }
 
 for_all_primitive_types! {define_primitive_array}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::test_util::*;
+
+    proptest! {
+        #[test]
+        fn test_i32_array_round_trip(data in prop::collection::vec(any::<Option<i32>>(), 0..100)) {
+            let array = I32Array::from_slice(&data);
+            check_array_eq(&array, &data);
+        }
+
+        #[test]
+        fn test_i64_array_round_trip(data in prop::collection::vec(any::<Option<i64>>(), 0..100)) {
+            let array = I64Array::from_slice(&data);
+            check_array_eq(&array, &data);
+        }
+
+        #[test]
+        fn test_f64_array_round_trip(data in prop::collection::vec(prop::option::of(prop::num::f64::ANY), 0..100)) {
+            // NaN, infinities and -0.0 are included, so items are compared by their bits.
+            let array = F64Array::from_slice(&data);
+            let to_bits = |item: Option<f64>| item.map(f64::to_bits);
+            prop_assert_eq!(
+                array.iter().map(to_bits).collect::<Vec<_>>(),
+                data.iter().copied().map(to_bits).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_i32_array_slice(
+            data in prop::collection::vec(any::<Option<i32>>(), 0..100),
+            a in 0..100usize,
+            b in 0..100usize,
+        ) {
+            let (start, end) = (a.min(b).min(data.len()), a.max(b).min(data.len()));
+            let array = I32Array::from_slice(&data).slice(start, end - start);
+            check_array_eq(&array, &data[start..end]);
+        }
+    }
+
+    #[test]
+    fn test_slice_of_slice() {
+        let data = [Some(1.0), None, Some(3.0), Some(4.0), None, Some(6.0)];
+        let array = F64Array::from_slice(&data);
+        let slice = array.slice(1, 4);
+        check_array_eq(&slice, &data[1..5]);
+        check_array_eq(&slice.slice(2, 2), &data[3..5]);
+        assert!(slice.slice(4, 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_out_of_bounds() {
+        I64Array::from_slice(&[Some(1), Some(2)]).slice(1, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice out of bounds")]
+    fn test_slice_offset_overflow() {
+        I64Array::from_slice(&[Some(1), Some(2)]).slice(usize::MAX, 2);
+    }
+}
@@ -1,18 +1,25 @@
 use bitvec::vec::BitVec;
 
-use crate::array::{Array, ArrayBuilder};
+use crate::array::{Array, ArrayBuilder, Bitmap, Buffer};
 
+#[derive(Clone)]
 pub struct StringArray {
     /// The flattened data of string.
-    data: Vec<u8>,
+    data: Buffer<u8>,
     /// Offsets of each string in the data flat array, plus an extra offset at the end.
-    offsets: Vec<usize>,
+    offsets: Buffer<usize>,
     /// The null bitmap of this array.
-    bitmap: BitVec,
+    bitmap: Bitmap,
+    /// Position of the first item of this array in `offsets` and `bitmap`.
+    offset: usize,
+    /// Number of items of this array.
+    len: usize,
 }
 
 impl StringArray {
     fn get(&self, idx: usize) -> Option<&str> {
+        assert!(idx < self.len, "index out of bounds");
+        let idx = self.offset + idx;
         if self.bitmap[idx] {
             let start = self.offsets[idx];
             let end = self.offsets[idx + 1];
@@ -23,7 +30,21 @@ impl StringArray {
     }
 
     fn len(&self) -> usize {
-        self.offsets.len() - 1
+        self.len
+    }
+
+    fn slice(&self, offset: usize, len: usize) -> Self {
+        assert!(
+            offset.checked_add(len).is_some_and(|end| end <= self.len),
+            "slice out of bounds"
+        );
+        Self {
+            data: self.data.clone(),
+            offsets: self.offsets.clone(),
+            bitmap: self.bitmap.clone(),
+            offset: self.offset + offset,
+            len,
+        }
     }
 }
 
@@ -37,21 +58,67 @@ impl Array for StringArray {
     fn len(&self) -> usize {
         self.len()
     }
+    fn slice(&self, offset: usize, len: usize) -> Self {
+        self.slice(offset, len)
+    }
 }
 
-pub type StringArrayBuilder = StringArray;
+/// Builds a [`StringArray`].
+pub struct StringArrayBuilder {
+    /// The flattened data of string.
+    data: Vec<u8>,
+    /// Offsets of each string in the data flat array, plus an extra offset at the end.
+    offsets: Vec<usize>,
+    /// The null bitmap of this array.
+    bitmap: BitVec,
+}
+
+impl StringArrayBuilder {
+    /// Push a non-null string written by `f` straight into the data buffer, without building
+    /// it as a `String` first. The string is committed once `f` returns.
+    pub fn push_with<R>(&mut self, f: impl FnOnce(&mut StringWriter<'_>) -> R) -> R {
+        let result = f(&mut StringWriter {
+            data: &mut self.data,
+        });
+        self.bitmap.push(true);
+        self.offsets.push(self.data.len());
+        result
+    }
+}
+
+/// Appends to the string being pushed by [`StringArrayBuilder::push_with`].
+pub struct StringWriter<'a> {
+    data: &'a mut Vec<u8>,
+}
+
+impl StringWriter<'_> {
+    pub fn push_str(&mut self, s: &str) {
+        self.data.extend_from_slice(s.as_bytes());
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+}
+
+impl std::fmt::Write for StringWriter<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
 
 impl ArrayBuilder for StringArrayBuilder {
     type Array = StringArray;
 
     fn with_capacity(capacity: usize) -> Self {
-        let mut this = Self {
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(0);
+        Self {
             data: Vec::new(),
-            offsets: Vec::with_capacity(capacity + 1),
+            offsets,
             bitmap: BitVec::with_capacity(capacity),
-        };
-        this.offsets.push(0);
-        this
+        }
     }
 
     fn push(&mut self, item: Option<<Self::Array as Array>::RefItem<'_>>) {
@@ -63,6 +130,65 @@ impl ArrayBuilder for StringArrayBuilder {
     }
 
     fn finish(self) -> Self::Array {
-        self
+        StringArray {
+            len: self.bitmap.len(),
+            data: self.data.into(),
+            offsets: self.offsets.into(),
+            bitmap: self.bitmap.into(),
+            offset: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::ArrayImpl;
+    use crate::scalar::ScalarRefImpl;
+    use crate::test_util::*;
+
+    #[test]
+    fn test_string_array_slice() {
+        let data = [Some("a"), None, Some("ccc"), Some(""), None, Some("ffffff")];
+        let array = StringArray::from_slice(&data);
+        for start in 0..=data.len() {
+            for end in start..=data.len() {
+                check_array_eq(&array.slice(start, end - start), &data[start..end]);
+            }
+        }
+        check_array_eq(&array.slice(1, 4).slice(1, 2), &data[2..4]);
+    }
+
+    #[test]
+    fn test_string_array_clone() {
+        let data = [Some("a"), None, Some("bc")];
+        let array = StringArray::from_slice(&data);
+        let cloned = array.clone();
+        assert!(std::ptr::eq(array.data.as_ptr(), cloned.data.as_ptr()));
+        check_array_eq(&cloned, &data);
+
+        let array: ArrayImpl = array.slice(1, 2).into();
+        let cloned = array.clone();
+        drop(array);
+        assert_eq!(cloned.get(1), Some(ScalarRefImpl::String("bc")));
+    }
+
+    #[test]
+    fn test_string_array_push_with() {
+        use std::fmt::Write;
+
+        let mut builder = StringArrayBuilder::with_capacity(4);
+        builder.push(Some("a"));
+        builder.push_with(|writer| {
+            writer.push_str("bc");
+            writer.push('ď');
+            write!(writer, "{}", 12).unwrap();
+        });
+        builder.push(None);
+        builder.push_with(|_| {});
+        check_array_eq(
+            &builder.finish(),
+            &[Some("a"), Some("bcď12"), None, Some("")],
+        );
     }
 }
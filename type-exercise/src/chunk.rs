@@ -0,0 +1,286 @@
+use crate::array::ArrayImpl;
+use crate::datatype::DataType;
+
+/// A named and typed column in a [`Schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub data_type: DataType,
+    pub nullable: bool,
+}
+
+impl Field {
+    pub fn new(name: impl Into<String>, data_type: DataType, nullable: bool) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            nullable,
+        }
+    }
+}
+
+/// The fields of the columns of a [`DataChunk`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schema {
+    fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Self { fields }
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Get the field at `idx`.
+    pub fn field(&self, idx: usize) -> Option<&Field> {
+        self.fields.get(idx)
+    }
+
+    /// Get the index of the first field named `name`.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|field| field.name == name)
+    }
+
+    /// Number of fields in the schema.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Check if the schema has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DataChunkError {
+    #[error("schema has {expected} fields, but {actual} columns are given")]
+    ColumnCountMismatch { expected: usize, actual: usize },
+    #[error("column `{name}` has type {actual}, but the schema says {expected}")]
+    TypeMismatch {
+        name: String,
+        expected: DataType,
+        actual: DataType,
+    },
+    #[error("column `{name}` has {actual} rows, but the chunk has {expected}")]
+    LengthMismatch {
+        name: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("column `{name}` is not nullable, but contains nulls")]
+    UnexpectedNull { name: String },
+    #[error("column index {0} is out of bounds")]
+    ColumnIndexOutOfBounds(usize),
+}
+
+/// A batch of rows stored as equal-length columns described by a [`Schema`].
+#[derive(Clone)]
+pub struct DataChunk {
+    schema: Schema,
+    columns: Vec<ArrayImpl>,
+    cardinality: usize,
+}
+
+impl DataChunk {
+    /// Create a chunk, checking that `columns` match `schema` in count, type, length and
+    /// nullability.
+    pub fn try_new(schema: Schema, columns: Vec<ArrayImpl>) -> Result<Self, DataChunkError> {
+        if schema.len() != columns.len() {
+            return Err(DataChunkError::ColumnCountMismatch {
+                expected: schema.len(),
+                actual: columns.len(),
+            });
+        }
+        let cardinality = columns.first().map_or(0, |column| column.len());
+        for (field, column) in schema.fields().iter().zip(&columns) {
+            if field.data_type != column.data_type() {
+                return Err(DataChunkError::TypeMismatch {
+                    name: field.name.clone(),
+                    expected: field.data_type,
+                    actual: column.data_type(),
+                });
+            }
+            if column.len() != cardinality {
+                return Err(DataChunkError::LengthMismatch {
+                    name: field.name.clone(),
+                    expected: cardinality,
+                    actual: column.len(),
+                });
+            }
+            if !field.nullable && (0..column.len()).any(|idx| column.get(idx).is_none()) {
+                return Err(DataChunkError::UnexpectedNull {
+                    name: field.name.clone(),
+                });
+            }
+        }
+        Ok(Self {
+            schema,
+            columns,
+            cardinality,
+        })
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn columns(&self) -> &[ArrayImpl] {
+        &self.columns
+    }
+
+    /// Get the column at `idx`.
+    pub fn column(&self, idx: usize) -> Option<&ArrayImpl> {
+        self.columns.get(idx)
+    }
+
+    /// Get the first column named `name`.
+    pub fn column_by_name(&self, name: &str) -> Option<&ArrayImpl> {
+        self.schema.index_of(name).map(|idx| &self.columns[idx])
+    }
+
+    /// Number of rows in the chunk.
+    pub fn cardinality(&self) -> usize {
+        self.cardinality
+    }
+
+    /// Number of columns in the chunk.
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Create a chunk with the columns at `indices`, in that order. Columns are shared with this
+    /// chunk rather than copied.
+    pub fn project(&self, indices: &[usize]) -> Result<Self, DataChunkError> {
+        let mut fields = Vec::with_capacity(indices.len());
+        let mut columns = Vec::with_capacity(indices.len());
+        for &idx in indices {
+            let column = self
+                .column(idx)
+                .ok_or(DataChunkError::ColumnIndexOutOfBounds(idx))?;
+            fields.push(self.schema.fields[idx].clone());
+            columns.push(column.clone());
+        }
+        Ok(Self {
+            schema: Schema::new(fields),
+            columns,
+            cardinality: self.cardinality,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::*;
+    use crate::expr::*;
+    use crate::scalar::ScalarRefImpl;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::String, true),
+        ])
+    }
+
+    fn chunk() -> DataChunk {
+        DataChunk::try_new(
+            schema(),
+            vec![
+                I32Array::from_slice(&[Some(1), Some(2), Some(3)]).into(),
+                StringArray::from_slice(&[Some("x"), None, Some("z")]).into(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_data_chunk() {
+        let chunk = chunk();
+        assert_eq!(chunk.cardinality(), 3);
+        assert_eq!(chunk.num_columns(), 2);
+        assert_eq!(
+            chunk.column_by_name("b").unwrap().data_type(),
+            DataType::String
+        );
+        assert!(chunk.column_by_name("c").is_none());
+
+        let projected = chunk.project(&[1, 1, 0]).unwrap();
+        assert_eq!(projected.cardinality(), 3);
+        assert_eq!(projected.schema().field(2).unwrap().name, "a");
+        assert_eq!(
+            projected.column(0).unwrap().get(2),
+            Some(ScalarRefImpl::String("z"))
+        );
+        assert!(matches!(
+            chunk.project(&[2]),
+            Err(DataChunkError::ColumnIndexOutOfBounds(2))
+        ));
+    }
+
+    #[test]
+    fn test_data_chunk_validation() {
+        let i32s = || I32Array::from_slice(&[Some(1), Some(2), Some(3)]).into();
+        assert!(matches!(
+            DataChunk::try_new(schema(), vec![i32s()]),
+            Err(DataChunkError::ColumnCountMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            DataChunk::try_new(schema(), vec![i32s(), i32s()]),
+            Err(DataChunkError::TypeMismatch { name, .. }) if name == "b"
+        ));
+        assert!(matches!(
+            DataChunk::try_new(
+                schema(),
+                vec![i32s(), StringArray::from_slice(&[Some("x")]).into()]
+            ),
+            Err(DataChunkError::LengthMismatch {
+                expected: 3,
+                actual: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            DataChunk::try_new(
+                schema(),
+                vec![
+                    I32Array::from_slice(&[Some(1), None]).into(),
+                    StringArray::from_slice(&[None, None]).into()
+                ]
+            ),
+            Err(DataChunkError::UnexpectedNull { name }) if name == "a"
+        ));
+    }
+
+    #[test]
+    fn test_eval_chunk() {
+        let chunk = DataChunk::try_new(
+            Schema::new(vec![
+                Field::new("a", DataType::String, true),
+                Field::new("b", DataType::String, true),
+            ]),
+            vec![
+                StringArray::from_slice(&[Some("000"), Some("111"), None]).into(),
+                StringArray::from_slice(&[Some("0"), Some("0"), Some("0")]).into(),
+            ],
+        )
+        .unwrap();
+        let result = build_binary_expression(
+            ExpressionFunc::StrContains,
+            DataType::String,
+            DataType::String,
+        )
+        .unwrap()
+        .eval_chunk(&chunk)
+        .unwrap();
+        assert_eq!(result.get(0), Some(ScalarRefImpl::Bool(true)));
+        assert_eq!(result.get(1), Some(ScalarRefImpl::Bool(false)));
+        assert_eq!(result.get(2), None);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::macros::{for_all_comparable_type_pairs, for_all_types};
+
+macro_rules! define_data_type {
+    ($({ $Name:ident, $Variant:ident, $Array:ident, $ArrayBuilder:ident, $Owned:ty, $Ref:ty }),*) => {
+        /// The logical type of an array or a scalar.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum DataType {
+            $(
+                $Name,
+            )*
+        }
+
+        impl std::fmt::Display for DataType {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(
+                        Self::$Name => write!(f, stringify!($Name)),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+for_all_types! { define_data_type }
+
+macro_rules! impl_common_type {
+    ($({ $Name1:ident, $Array1:ident, $Owned1:ty, $Name2:ident, $Array2:ident, $Owned2:ty, $NameC:ident, $ArrayC:ident, $OwnedC:ty }),*) => {
+        impl DataType {
+            /// The type values of `self` and `other` are converted to before comparing them, or
+            /// `None` if they cannot be compared.
+            pub fn common_type(self, other: Self) -> Option<Self> {
+                match (self, other) {
+                    $(
+                        (Self::$Name1, Self::$Name2) => Some(Self::$NameC),
+                    )*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+for_all_comparable_type_pairs! { impl_common_type }
+
+#[cfg(test)]
+mod tests {
+    use super::DataType;
+    use crate::array::*;
+    use crate::scalar::{ScalarImpl, ScalarRefImpl};
+
+    #[test]
+    fn test_data_type() {
+        let arrays: Vec<ArrayImpl> = vec![
+            I32Array::from_slice(&[Some(1)]).into(),
+            I64Array::from_slice(&[Some(1)]).into(),
+            F64Array::from_slice(&[Some(1.0)]).into(),
+            BoolArray::from_slice(&[Some(true)]).into(),
+            StringArray::from_slice(&[Some("1")]).into(),
+        ];
+        let scalars = [
+            ScalarImpl::I32(1),
+            ScalarImpl::I64(1),
+            ScalarImpl::F64(1.0),
+            ScalarImpl::Bool(true),
+            ScalarImpl::String("1".to_string()),
+        ];
+        let data_types = [
+            DataType::Int32,
+            DataType::Int64,
+            DataType::Float64,
+            DataType::Bool,
+            DataType::String,
+        ];
+        for ((array, scalar), data_type) in arrays.iter().zip(&scalars).zip(data_types) {
+            assert_eq!(array.data_type(), data_type);
+            assert_eq!(array.get(0).unwrap().data_type(), data_type);
+            assert_eq!(scalar.data_type(), data_type);
+
+            let mut builder = ArrayBuilderImpl::with_capacity(data_type, 1);
+            builder.push(array.get(0));
+            builder.push(None);
+            let built = builder.finish();
+            assert_eq!(built.data_type(), data_type);
+            assert_eq!(built.get(0), array.get(0));
+            assert_eq!(built.get(1), None);
+        }
+        assert_eq!(ScalarRefImpl::String("1").data_type(), DataType::String);
+        assert_eq!(DataType::Float64.to_string(), "Float64");
+    }
+
+    #[test]
+    fn test_common_type() {
+        use DataType::*;
+        assert_eq!(Int32.common_type(Int32), Some(Int32));
+        assert_eq!(Int32.common_type(Int64), Some(Int64));
+        assert_eq!(Int64.common_type(Int32), Some(Int64));
+        assert_eq!(Int64.common_type(Float64), Some(Float64));
+        assert_eq!(String.common_type(String), Some(String));
+        assert_eq!(Bool.common_type(Int32), None);
+        assert_eq!(String.common_type(Float64), None);
+    }
+}
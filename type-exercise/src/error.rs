@@ -0,0 +1,52 @@
+use crate::datatype::DataType;
+use crate::UnsupportedTypes;
+
+/// Error of converting an array, builder or scalar into a concrete type. A dictionary-encoded
+/// array converted without decoding it reports the same type on both sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("type mismatch: expected {expected}, got {}{actual}", encoding(.expected, .actual))]
+pub struct TypeMismatch {
+    pub expected: DataType,
+    pub actual: DataType,
+}
+
+/// Error of evaluating an expression.
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    #[error("type mismatch: expected {expected}, got {}{actual}", encoding(.expected, .actual))]
+    TypeMismatch {
+        expected: DataType,
+        actual: DataType,
+    },
+    #[error("length mismatch: {left} and {right}")]
+    LengthMismatch { left: usize, right: usize },
+    #[error("expected {expected} arguments, got {actual}")]
+    ArityMismatch { expected: usize, actual: usize },
+    #[error("numeric overflow")]
+    Overflow,
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error(transparent)]
+    UnsupportedTypes(#[from] UnsupportedTypes),
+}
+
+impl From<TypeMismatch> for EvalError {
+    fn from(e: TypeMismatch) -> Self {
+        Self::TypeMismatch {
+            expected: e.expected,
+            actual: e.actual,
+        }
+    }
+}
+
+/// Describe the encoding of the actual type of a type mismatch, which can only be the cause if
+/// the types are the same.
+fn encoding(expected: &DataType, actual: &DataType) -> &'static str {
+    if expected == actual {
+        "dictionary-encoded "
+    } else {
+        ""
+    }
+}
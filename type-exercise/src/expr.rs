@@ -1,19 +1,49 @@
+use std::sync::OnceLock;
+
+pub use arithmetic::*;
 pub use binary::*;
 pub use cmp::*;
+pub use conditional::*;
+pub use logical::*;
+pub use mode::*;
+pub use node::*;
+pub use registry::*;
 pub use string::*;
+pub use ternary::*;
+pub use unary::*;
+pub use variadic::*;
 
-use crate::array::{ArrayImpl, BoolArray, I32Array, StringArray};
+use crate::array::ArrayImpl;
+use crate::chunk::DataChunk;
+use crate::datatype::DataType;
+use crate::EvalError;
 
+mod arithmetic;
 mod binary;
 mod cmp;
+mod conditional;
+mod logical;
+mod mode;
+mod node;
+mod registry;
 mod string;
+mod ternary;
+mod unary;
+mod variadic;
 
 pub trait Expression {
     /// Evaluate the expression with the given input arrays.
-    fn eval_expr(&self, data: &[&ArrayImpl]) -> anyhow::Result<ArrayImpl>;
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl, EvalError>;
+
+    /// Evaluate the expression with the columns of `chunk` as input arrays.
+    fn eval_chunk(&self, chunk: &DataChunk) -> Result<ArrayImpl, EvalError> {
+        let columns: Vec<_> = chunk.columns().iter().collect();
+        self.eval_expr(&columns)
+    }
 }
 
 /// All supported expression functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExpressionFunc {
     CmpLt,
     CmpLe,
@@ -23,48 +53,75 @@ pub enum ExpressionFunc {
     CmpNe,
     StrContains,
     StrConcat,
+    And,
+    Or,
+    Xor,
 }
 
-pub fn build_binary_expression(f: ExpressionFunc) -> Box<dyn Expression> {
-    use ExpressionFunc::*;
-
-    match f {
-        CmpLt => Box::new(BinaryExpression::<I32Array, I32Array, BoolArray, _>::new(
-            cmp_lt::<i32, i32, i32>,
-        )),
-        CmpLe => Box::new(BinaryExpression::<I32Array, I32Array, BoolArray, _>::new(
-            cmp_le::<i32, i32, i32>,
-        )),
-        CmpGt => Box::new(BinaryExpression::<I32Array, I32Array, BoolArray, _>::new(
-            cmp_gt::<i32, i32, i32>,
-        )),
-        CmpGe => Box::new(BinaryExpression::<I32Array, I32Array, BoolArray, _>::new(
-            cmp_ge::<i32, i32, i32>,
-        )),
-        CmpEq => Box::new(BinaryExpression::<I32Array, I32Array, BoolArray, _>::new(
-            cmp_eq::<i32, i32, i32>,
-        )),
-        CmpNe => Box::new(BinaryExpression::<I32Array, I32Array, BoolArray, _>::new(
-            cmp_ne::<i32, i32, i32>,
-        )),
-        StrContains => {
-            Box::new(BinaryExpression::<StringArray, StringArray, BoolArray, _>::new(str_contains))
-        }
-        StrConcat => {
-            Box::new(BinaryExpression::<StringArray, StringArray, BoolArray, _>::new(str_contains))
+impl ExpressionFunc {
+    /// All variants of [`ExpressionFunc`].
+    pub const ALL: [Self; 11] = [
+        Self::CmpLt,
+        Self::CmpLe,
+        Self::CmpGt,
+        Self::CmpGe,
+        Self::CmpEq,
+        Self::CmpNe,
+        Self::StrContains,
+        Self::StrConcat,
+        Self::And,
+        Self::Or,
+        Self::Xor,
+    ];
+}
+
+impl ExpressionFunc {
+    /// The name of this function in [`FunctionRegistry`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CmpLt => "cmp_lt",
+            Self::CmpLe => "cmp_le",
+            Self::CmpGt => "cmp_gt",
+            Self::CmpGe => "cmp_ge",
+            Self::CmpEq => "cmp_eq",
+            Self::CmpNe => "cmp_ne",
+            Self::StrContains => "str_contains",
+            Self::StrConcat => "str_concat",
+            Self::And => "and",
+            Self::Or => "or",
+            Self::Xor => "xor",
         }
     }
 }
 
+/// Build the expression of `f` on arguments of types `left` and `right`, picking the overload
+/// from the built-in functions of [`FunctionRegistry`].
+pub fn build_binary_expression(
+    f: ExpressionFunc,
+    left: DataType,
+    right: DataType,
+) -> Result<Box<dyn Expression>, FunctionError> {
+    static BUILTINS: OnceLock<FunctionRegistry> = OnceLock::new();
+    BUILTINS
+        .get_or_init(FunctionRegistry::with_builtins)
+        .build(f.name(), &[left, right])
+}
+
 #[cfg(test)]
 mod test {
     use crate::array::*;
+    use crate::datatype::DataType;
     use crate::expr::*;
     use crate::scalar::ScalarRefImpl;
 
     #[test]
     fn test_build_str_contains() {
-        let expr = build_binary_expression(ExpressionFunc::StrContains);
+        let expr = build_binary_expression(
+            ExpressionFunc::StrContains,
+            DataType::String,
+            DataType::String,
+        )
+        .unwrap();
 
         for _ in 0..10 {
             let result = expr
@@ -78,4 +135,92 @@ mod test {
             assert!(result.get(2).is_none());
         }
     }
+
+    /// The output type of `f` on two arguments of `arg_type`, or `None` if it does not accept
+    /// them.
+    fn output_type(f: ExpressionFunc, arg_type: DataType) -> Option<DataType> {
+        use ExpressionFunc::*;
+        match (f, arg_type) {
+            (CmpLt | CmpLe | CmpGt | CmpGe | CmpEq | CmpNe, _) => Some(DataType::Bool),
+            (StrContains, DataType::String) => Some(DataType::Bool),
+            (StrConcat, DataType::String) => Some(DataType::String),
+            (And | Or | Xor, DataType::Bool) => Some(DataType::Bool),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_build_all_funcs() {
+        let arg_types = [
+            DataType::Int32,
+            DataType::Int64,
+            DataType::Float64,
+            DataType::Bool,
+            DataType::String,
+        ];
+        for f in ExpressionFunc::ALL {
+            for arg_type in arg_types {
+                let expr = build_binary_expression(f, arg_type, arg_type);
+                let Some(output_type) = output_type(f, arg_type) else {
+                    assert!(
+                        matches!(expr, Err(FunctionError::NoMatchingOverload { .. })),
+                        "{f:?}({arg_type})"
+                    );
+                    continue;
+                };
+                let mut builder = ArrayBuilderImpl::with_capacity(arg_type, 2);
+                builder.push(None);
+                builder.push(None);
+                let args = builder.finish();
+
+                let result = expr.unwrap().eval_expr(&[&args, &args]).unwrap();
+                assert_eq!(result.data_type(), output_type, "{f:?}({arg_type})");
+                assert_eq!(result.len(), 2, "{f:?}({arg_type})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_cmp_on_other_types() {
+        let expr =
+            build_binary_expression(ExpressionFunc::CmpLt, DataType::String, DataType::String)
+                .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &StringArray::from_slice(&[Some("a"), Some("b")]).into(),
+                &StringArray::from_slice(&[Some("b"), Some("a")]).into(),
+            ])
+            .unwrap();
+        assert_eq!(result.get(0), Some(ScalarRefImpl::Bool(true)));
+        assert_eq!(result.get(1), Some(ScalarRefImpl::Bool(false)));
+
+        let expr =
+            build_binary_expression(ExpressionFunc::CmpLt, DataType::Int64, DataType::Float64)
+                .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &I64Array::from_slice(&[Some(1)]).into(),
+                &F64Array::from_slice(&[Some(1.5)]).into(),
+            ])
+            .unwrap();
+        assert_eq!(result.get(0), Some(ScalarRefImpl::Bool(true)));
+    }
+
+    #[test]
+    fn test_build_str_concat() {
+        let result = build_binary_expression(
+            ExpressionFunc::StrConcat,
+            DataType::String,
+            DataType::String,
+        )
+        .unwrap()
+        .eval_expr(&[
+            &StringArray::from_slice(&[Some("00"), Some("1"), None]).into(),
+            &StringArray::from_slice(&[Some("0"), Some(""), Some("2")]).into(),
+        ])
+        .unwrap();
+        assert_eq!(result.get(0), Some(ScalarRefImpl::String("000")));
+        assert_eq!(result.get(1), Some(ScalarRefImpl::String("1")));
+        assert!(result.get(2).is_none());
+    }
 }
@@ -0,0 +1,314 @@
+use super::{BinaryExpression, FunctionRegistry, UnaryExpression};
+use crate::array::*;
+use crate::datatype::DataType;
+use crate::macros::{for_all_numeric_type_pairs, for_all_primitive_types};
+use crate::scalar::{PrimitiveType, Widen};
+use crate::EvalError;
+
+/// Numeric types whose arithmetic reports overflow and division by zero as [`EvalError`]
+/// instead of wrapping or panicking.
+pub trait Numeric: PrimitiveType + PartialOrd {
+    fn checked_add(self, rhs: Self) -> Result<Self, EvalError>;
+    fn checked_sub(self, rhs: Self) -> Result<Self, EvalError>;
+    fn checked_mul(self, rhs: Self) -> Result<Self, EvalError>;
+    fn checked_div(self, rhs: Self) -> Result<Self, EvalError>;
+    fn checked_rem(self, rhs: Self) -> Result<Self, EvalError>;
+    fn checked_neg(self) -> Result<Self, EvalError>;
+}
+
+macro_rules! impl_numeric_for_integers {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn checked_add(self, rhs: Self) -> Result<Self, EvalError> {
+                    <$t>::checked_add(self, rhs).ok_or(EvalError::Overflow)
+                }
+                fn checked_sub(self, rhs: Self) -> Result<Self, EvalError> {
+                    <$t>::checked_sub(self, rhs).ok_or(EvalError::Overflow)
+                }
+                fn checked_mul(self, rhs: Self) -> Result<Self, EvalError> {
+                    <$t>::checked_mul(self, rhs).ok_or(EvalError::Overflow)
+                }
+                fn checked_div(self, rhs: Self) -> Result<Self, EvalError> {
+                    if rhs == 0 {
+                        return Err(EvalError::DivisionByZero);
+                    }
+                    <$t>::checked_div(self, rhs).ok_or(EvalError::Overflow)
+                }
+                fn checked_rem(self, rhs: Self) -> Result<Self, EvalError> {
+                    if rhs == 0 {
+                        return Err(EvalError::DivisionByZero);
+                    }
+                    // `MIN % -1` is 0, it only overflows as a side effect of the division.
+                    Ok(self.wrapping_rem(rhs))
+                }
+                fn checked_neg(self) -> Result<Self, EvalError> {
+                    <$t>::checked_neg(self).ok_or(EvalError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_for_integers! { i32, i64 }
+
+/// Report an infinite result of finite operands as overflow, like SQL does.
+fn check_float(result: f64, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+    if result.is_infinite() && lhs.is_finite() && rhs.is_finite() {
+        Err(EvalError::Overflow)
+    } else {
+        Ok(result)
+    }
+}
+
+impl Numeric for f64 {
+    fn checked_add(self, rhs: Self) -> Result<Self, EvalError> {
+        check_float(self + rhs, self, rhs)
+    }
+    fn checked_sub(self, rhs: Self) -> Result<Self, EvalError> {
+        check_float(self - rhs, self, rhs)
+    }
+    fn checked_mul(self, rhs: Self) -> Result<Self, EvalError> {
+        check_float(self * rhs, self, rhs)
+    }
+    fn checked_div(self, rhs: Self) -> Result<Self, EvalError> {
+        if rhs == 0.0 {
+            return Err(EvalError::DivisionByZero);
+        }
+        check_float(self / rhs, self, rhs)
+    }
+    fn checked_rem(self, rhs: Self) -> Result<Self, EvalError> {
+        if rhs == 0.0 {
+            return Err(EvalError::DivisionByZero);
+        }
+        Ok(self % rhs)
+    }
+    fn checked_neg(self) -> Result<Self, EvalError> {
+        Ok(-self)
+    }
+}
+
+pub fn add<I1: Widen<C>, I2: Widen<C>, C: Numeric>(i1: I1, i2: I2) -> Result<C, EvalError> {
+    i1.widen().checked_add(i2.widen())
+}
+
+pub fn sub<I1: Widen<C>, I2: Widen<C>, C: Numeric>(i1: I1, i2: I2) -> Result<C, EvalError> {
+    i1.widen().checked_sub(i2.widen())
+}
+
+pub fn mul<I1: Widen<C>, I2: Widen<C>, C: Numeric>(i1: I1, i2: I2) -> Result<C, EvalError> {
+    i1.widen().checked_mul(i2.widen())
+}
+
+pub fn div<I1: Widen<C>, I2: Widen<C>, C: Numeric>(i1: I1, i2: I2) -> Result<C, EvalError> {
+    i1.widen().checked_div(i2.widen())
+}
+
+/// The remainder of `i1 / i2`, with the sign of `i1`.
+pub fn modulo<I1: Widen<C>, I2: Widen<C>, C: Numeric>(i1: I1, i2: I2) -> Result<C, EvalError> {
+    i1.widen().checked_rem(i2.widen())
+}
+
+pub fn neg<T: Numeric>(i: T) -> Result<T, EvalError> {
+    i.checked_neg()
+}
+
+pub fn abs<T: Numeric>(i: T) -> Result<T, EvalError> {
+    if i < T::default() {
+        i.checked_neg()
+    } else {
+        Ok(i)
+    }
+}
+
+macro_rules! impl_register_unary_arithmetic_functions {
+    ($({ $Name:ident, $Variant:ident, $Array:ident, $ArrayBuilder:ident, $Owned:ty, $Ref:ty }),*) => {
+        /// Register arithmetic functions of one numeric argument.
+        fn register_unary_arithmetic_functions(registry: &mut FunctionRegistry) {
+            $(
+                registry.register("abs", &[DataType::$Name], DataType::$Name, || {
+                    Box::new(UnaryExpression::<$Array, $Array, _, _>::new_checked(abs::<$Owned>))
+                });
+                registry.register("neg", &[DataType::$Name], DataType::$Name, || {
+                    Box::new(UnaryExpression::<$Array, $Array, _, _>::new_checked(neg::<$Owned>))
+                });
+            )*
+        }
+    };
+}
+
+for_all_primitive_types! { impl_register_unary_arithmetic_functions }
+
+macro_rules! impl_register_binary_arithmetic_functions {
+    ($({ $Name1:ident, $Array1:ident, $Owned1:ty, $Name2:ident, $Array2:ident, $Owned2:ty, $NameC:ident, $ArrayC:ident, $OwnedC:ty }),*) => {
+        /// Register arithmetic functions of two numeric arguments, computed in their common type.
+        fn register_binary_arithmetic_functions(registry: &mut FunctionRegistry) {
+            $(
+                let args = [DataType::$Name1, DataType::$Name2];
+                registry.register("add", &args, DataType::$NameC, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, $ArrayC, _, _>::new_checked(add::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("sub", &args, DataType::$NameC, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, $ArrayC, _, _>::new_checked(sub::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("mul", &args, DataType::$NameC, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, $ArrayC, _, _>::new_checked(mul::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("div", &args, DataType::$NameC, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, $ArrayC, _, _>::new_checked(div::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("mod", &args, DataType::$NameC, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, $ArrayC, _, _>::new_checked(modulo::<$Owned1, $Owned2, $OwnedC>))
+                });
+            )*
+        }
+    };
+}
+
+for_all_numeric_type_pairs! { impl_register_binary_arithmetic_functions }
+
+/// Register arithmetic functions on numeric types.
+pub(super) fn register_arithmetic_functions(registry: &mut FunctionRegistry) {
+    register_unary_arithmetic_functions(registry);
+    register_binary_arithmetic_functions(registry);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::test_util::*;
+    use crate::EvalError;
+
+    const NUMERIC_TYPES: [DataType; 3] = [DataType::Int32, DataType::Int64, DataType::Float64];
+
+    #[test]
+    fn test_registry_widens() {
+        let registry = FunctionRegistry::with_builtins();
+        let rank = |ty| NUMERIC_TYPES.iter().position(|t| *t == ty).unwrap();
+        for left in NUMERIC_TYPES {
+            for right in NUMERIC_TYPES {
+                let common = NUMERIC_TYPES[rank(left).max(rank(right))];
+                for name in ["add", "sub", "mul", "div", "mod"] {
+                    let func = registry.lookup(name, &[left, right]).unwrap();
+                    assert_eq!(func.return_type(), common, "{name}({left}, {right})");
+                }
+            }
+        }
+        assert!(registry
+            .lookup("add", &[DataType::Int32, DataType::Bool])
+            .is_err());
+    }
+
+    #[test]
+    fn test_mixed_types() {
+        let registry = FunctionRegistry::with_builtins();
+        let result = registry
+            .build("add", &[DataType::Int32, DataType::Int64])
+            .unwrap()
+            .eval_expr(&[
+                &I32Array::from_slice(&[Some(i32::MAX), Some(1), None]).into(),
+                &I64Array::from_slice(&[Some(1), None, Some(1)]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<I64Array>(
+            (&result).try_into().unwrap(),
+            &[Some(i32::MAX as i64 + 1), None, None],
+        );
+
+        let result = registry
+            .build("div", &[DataType::Int64, DataType::Float64])
+            .unwrap()
+            .eval_expr(&[
+                &I64Array::from_slice(&[Some(1), Some(-7)]).into(),
+                &F64Array::from_slice(&[Some(4.0), Some(2.0)]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<F64Array>((&result).try_into().unwrap(), &[Some(0.25), Some(-3.5)]);
+    }
+
+    #[test]
+    fn test_integer_errors() {
+        assert!(matches!(
+            add::<i32, i32, i32>(i32::MAX, 1),
+            Err(EvalError::Overflow)
+        ));
+        assert!(matches!(
+            sub::<i64, i64, i64>(i64::MIN, 1),
+            Err(EvalError::Overflow)
+        ));
+        assert!(matches!(
+            mul::<i32, i32, i32>(1 << 16, 1 << 16),
+            Err(EvalError::Overflow)
+        ));
+        assert!(matches!(
+            div::<i32, i32, i32>(i32::MIN, -1),
+            Err(EvalError::Overflow)
+        ));
+        assert!(matches!(
+            div::<i32, i64, i64>(1, 0),
+            Err(EvalError::DivisionByZero)
+        ));
+        assert!(matches!(
+            modulo::<i64, i64, i64>(1, 0),
+            Err(EvalError::DivisionByZero)
+        ));
+        assert!(matches!(neg(i32::MIN), Err(EvalError::Overflow)));
+        assert!(matches!(abs(i64::MIN), Err(EvalError::Overflow)));
+        assert_eq!(modulo::<i32, i32, i32>(i32::MIN, -1).unwrap(), 0);
+        assert_eq!(modulo::<i32, i32, i32>(-7, 3).unwrap(), -1);
+        assert_eq!(div::<i32, i32, i32>(-7, 2).unwrap(), -3);
+    }
+
+    #[test]
+    fn test_float_errors() {
+        assert!(matches!(
+            mul::<f64, f64, f64>(f64::MAX, 2.0),
+            Err(EvalError::Overflow)
+        ));
+        assert!(matches!(
+            div::<f64, i32, f64>(1.0, 0),
+            Err(EvalError::DivisionByZero)
+        ));
+        assert!(matches!(
+            modulo::<f64, f64, f64>(1.0, 0.0),
+            Err(EvalError::DivisionByZero)
+        ));
+        assert_eq!(
+            add::<f64, f64, f64>(f64::INFINITY, 1.0).unwrap(),
+            f64::INFINITY
+        );
+        assert!(add::<f64, f64, f64>(f64::NAN, 1.0).unwrap().is_nan());
+        assert_eq!(modulo::<f64, f64, f64>(-7.5, 2.0).unwrap(), -1.5);
+    }
+
+    #[test]
+    fn test_checked_and_null_on_error() {
+        let i1: ArrayImpl = I32Array::from_slice(&[Some(6), Some(1), None, Some(i32::MIN)]).into();
+        let i2: ArrayImpl = I32Array::from_slice(&[Some(3), Some(0), Some(0), Some(-1)]).into();
+
+        let expr = BinaryExpression::<I32Array, I32Array, I32Array, _, _>::new_checked(
+            div::<i32, i32, i32>,
+        );
+        assert!(matches!(
+            expr.eval(&i1, &i2),
+            Err(EvalError::DivisionByZero)
+        ));
+        let result = expr.eval(&i1.slice(2, 1), &i2.slice(2, 1)).unwrap();
+        check_array_eq::<I32Array>((&result).try_into().unwrap(), &[None]);
+
+        let expr = BinaryExpression::<I32Array, I32Array, I32Array, _, _>::new_null_on_error(
+            div::<i32, i32, i32>,
+        );
+        let result = expr.eval(&i1, &i2).unwrap();
+        check_array_eq::<I32Array>((&result).try_into().unwrap(), &[Some(2), None, None, None]);
+
+        let expr = UnaryExpression::<I32Array, I32Array, _, _>::new_null_on_error(neg::<i32>);
+        let result = expr.eval(&i1).unwrap();
+        check_array_eq::<I32Array>(
+            (&result).try_into().unwrap(),
+            &[Some(-6), Some(-1), None, None],
+        );
+    }
+}
@@ -1,14 +1,17 @@
-use super::Expression;
-use crate::array::{Array, ArrayBuilder, ArrayImpl};
-use crate::scalar::Scalar;
-use crate::TypeMismatch;
+use super::{BinaryMode, Checked, Expression, NullAware, NullOnError, Strict, Writer};
+use crate::array::{Array, ArrayBuilder, ArrayImpl, StringArray};
+use crate::scalar::{ScalarImpl, ScalarRefImpl};
+use crate::{EvalError, TypeMismatch};
 
-pub struct BinaryExpression<I1, I2, O, F> {
+/// An expression of two arguments. `N` picks the null and error semantics, see [`Strict`],
+/// [`NullAware`], [`Checked`], [`NullOnError`] and [`Writer`].
+pub struct BinaryExpression<I1, I2, O, F, N = Strict> {
     func: F,
-    _phantom: std::marker::PhantomData<(I1, I2, O)>,
+    _phantom: std::marker::PhantomData<(I1, I2, O, N)>,
 }
 
 impl<I1: Array, I2: Array, O: Array, F> BinaryExpression<I1, I2, O, F> {
+    /// Create an expression that returns null if any argument is null.
     pub fn new(func: F) -> Self {
         Self {
             func,
@@ -16,40 +19,281 @@ impl<I1: Array, I2: Array, O: Array, F> BinaryExpression<I1, I2, O, F> {
         }
     }
 
-    pub fn eval(&self, i1: &ArrayImpl, i2: &ArrayImpl) -> anyhow::Result<ArrayImpl>
+    /// Evaluate the expression on two scalars.
+    pub fn eval_scalar_scalar(
+        &self,
+        i1: ScalarRefImpl<'_>,
+        i2: ScalarRefImpl<'_>,
+    ) -> Result<ScalarImpl, EvalError>
+    where
+        for<'a> I1::RefItem<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+        for<'a> I2::RefItem<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+        F: Fn(I1::RefItem<'_>, I2::RefItem<'_>) -> O::OwnedItem,
+        O::OwnedItem: Into<ScalarImpl>,
+    {
+        let i1: I1::RefItem<'_> = i1.try_into()?;
+        let i2: I2::RefItem<'_> = i2.try_into()?;
+        Ok((self.func)(i1, i2).into())
+    }
+}
+
+impl<I1: Array, I2: Array, O: Array, F> BinaryExpression<I1, I2, O, F, NullAware> {
+    /// Create an expression whose function is called on nullable arguments.
+    pub fn new_null_aware(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Evaluate the expression on two nullable scalars.
+    pub fn eval_scalar_scalar(
+        &self,
+        i1: Option<ScalarRefImpl<'_>>,
+        i2: Option<ScalarRefImpl<'_>>,
+    ) -> Result<Option<ScalarImpl>, EvalError>
+    where
+        for<'a> I1::RefItem<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+        for<'a> I2::RefItem<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+        F: Fn(Option<I1::RefItem<'_>>, Option<I2::RefItem<'_>>) -> Option<O::OwnedItem>,
+        O::OwnedItem: Into<ScalarImpl>,
+    {
+        let i1: Option<I1::RefItem<'_>> = i1.map(TryInto::try_into).transpose()?;
+        let i2: Option<I2::RefItem<'_>> = i2.map(TryInto::try_into).transpose()?;
+        Ok((self.func)(i1, i2).map(Into::into))
+    }
+}
+
+impl<I1: Array, I2: Array, O: Array, F> BinaryExpression<I1, I2, O, F, Checked> {
+    /// Create an expression whose function may fail, which fails the evaluation.
+    pub fn new_checked(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I1: Array, I2: Array, O: Array, F> BinaryExpression<I1, I2, O, F, NullOnError> {
+    /// Create an expression whose function may fail, which makes the result of that row null.
+    pub fn new_null_on_error(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I1: Array, I2: Array, F> BinaryExpression<I1, I2, StringArray, F, Writer> {
+    /// Create an expression whose function writes its string result through a
+    /// [`StringWriter`](crate::array::StringWriter).
+    pub fn new_writer(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I1: Array, I2: Array, O: Array, F, N: BinaryMode<I1, I2, O, F>>
+    BinaryExpression<I1, I2, O, F, N>
+{
+    pub fn eval(&self, i1: &ArrayImpl, i2: &ArrayImpl) -> Result<ArrayImpl, EvalError>
     where
         for<'a> &'a I1: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
         for<'a> &'a I2: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
-        F: Fn(I1::RefItem<'_>, I2::RefItem<'_>) -> O::OwnedItem,
         O: Into<ArrayImpl>,
     {
-        let i1: &I1 = i1.try_into()?;
-        let i2: &I2 = i2.try_into()?;
-        assert_eq!(i1.len(), i2.len(), "array length mismatch");
+        let (i1, i2) = (i1.decode(), i2.decode());
+        let i1: &I1 = (&*i1).try_into()?;
+        let i2: &I2 = (&*i2).try_into()?;
+        Ok(self.eval_array(i1, i2)?.into())
+    }
+
+    /// Evaluate the expression on two arrays of concrete types.
+    pub fn eval_array(&self, i1: &I1, i2: &I2) -> Result<O, EvalError> {
+        if i1.len() != i2.len() {
+            return Err(EvalError::LengthMismatch {
+                left: i1.len(),
+                right: i2.len(),
+            });
+        }
 
         let mut builder = O::Builder::with_capacity(i1.len());
         for (a, b) in i1.iter().zip(i2.iter()) {
-            match (a, b) {
-                (Some(a), Some(b)) => builder.push(Some((self.func)(a, b).as_scalar_ref())),
-                _ => builder.push(None),
-            }
+            N::eval_row(&self.func, a, b, &mut builder)?;
+        }
+
+        Ok(builder.finish())
+    }
+
+    /// Evaluate the expression on an array and a scalar, which is used as the right operand of
+    /// every row.
+    pub fn eval_array_scalar(
+        &self,
+        i1: &ArrayImpl,
+        i2: ScalarRefImpl<'_>,
+    ) -> Result<ArrayImpl, EvalError>
+    where
+        for<'a> &'a I1: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+        for<'a> I2::RefItem<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+        O: Into<ArrayImpl>,
+    {
+        if let ArrayImpl::Dictionary(dict) = i1 {
+            return dict.map_entries(|i1| self.eval_array_scalar(i1, i2));
+        }
+        let i1: &I1 = i1.try_into()?;
+        let i2: I2::RefItem<'_> = i2.try_into()?;
+
+        let mut builder = O::Builder::with_capacity(i1.len());
+        for a in i1.iter() {
+            N::eval_row(&self.func, a, Some(i2), &mut builder)?;
+        }
+
+        Ok(builder.finish().into())
+    }
+
+    /// Evaluate the expression on a scalar and an array, which is used as the left operand of
+    /// every row.
+    pub fn eval_scalar_array(
+        &self,
+        i1: ScalarRefImpl<'_>,
+        i2: &ArrayImpl,
+    ) -> Result<ArrayImpl, EvalError>
+    where
+        for<'a> I1::RefItem<'a>: TryFrom<ScalarRefImpl<'a>, Error = TypeMismatch>,
+        for<'a> &'a I2: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+        O: Into<ArrayImpl>,
+    {
+        if let ArrayImpl::Dictionary(dict) = i2 {
+            return dict.map_entries(|i2| self.eval_scalar_array(i1, i2));
+        }
+        let i1: I1::RefItem<'_> = i1.try_into()?;
+        let i2: &I2 = i2.try_into()?;
+
+        let mut builder = O::Builder::with_capacity(i2.len());
+        for b in i2.iter() {
+            N::eval_row(&self.func, Some(i1), b, &mut builder)?;
         }
 
         Ok(builder.finish().into())
     }
 }
 
-impl<I1: Array, I2: Array, O: Array, F> Expression for BinaryExpression<I1, I2, O, F>
+impl<I1: Array, I2: Array, O: Array, F, N> Expression for BinaryExpression<I1, I2, O, F, N>
 where
     for<'a> &'a I1: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
     for<'a> &'a I2: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
-    F: Fn(I1::RefItem<'_>, I2::RefItem<'_>) -> O::OwnedItem,
+    N: BinaryMode<I1, I2, O, F>,
     O: Into<ArrayImpl>,
 {
-    fn eval_expr(&self, data: &[&ArrayImpl]) -> anyhow::Result<ArrayImpl> {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl, EvalError> {
         if data.len() != 2 {
-            anyhow::bail!("BinaryExpression requires 2 arguments");
+            return Err(EvalError::ArityMismatch {
+                expected: 2,
+                actual: data.len(),
+            });
         }
         self.eval(data[0], data[1])
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::scalar::{ScalarImpl, ScalarRefImpl};
+    use crate::test_util::*;
+    use crate::EvalError;
+
+    #[test]
+    fn test_eval_on_slices() {
+        let expr =
+            BinaryExpression::<I32Array, I64Array, BoolArray, _>::new(cmp_lt::<i32, i64, i64>);
+        let i1: ArrayImpl =
+            I32Array::from_slice(&[Some(1), Some(2), None, Some(4), Some(5)]).into();
+        let i2: ArrayImpl =
+            I64Array::from_slice(&[Some(9), Some(3), Some(3), Some(3), None, Some(3)]).into();
+        let result = expr.eval(&i1.slice(1, 3), &i2.slice(2, 3)).unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[Some(true), None, None]);
+
+        let result = expr.eval(&i1.slice(3, 1), &i2.slice(0, 1)).unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[Some(true)]);
+    }
+
+    #[test]
+    fn test_eval_with_scalars() {
+        let expr = BinaryExpression::<StringArray, StringArray, StringArray, _>::new(
+            |a: &str, b: &str| a.to_owned() + b,
+        );
+        let array: ArrayImpl = StringArray::from_slice(&[Some("a"), None, Some("c")]).into();
+
+        let result = expr
+            .eval_array_scalar(&array, ScalarRefImpl::String("x"))
+            .unwrap();
+        check_array_eq::<StringArray>(
+            (&result).try_into().unwrap(),
+            &[Some("ax"), None, Some("cx")],
+        );
+
+        let result = expr
+            .eval_scalar_array(ScalarRefImpl::String("x"), &array.slice(1, 2))
+            .unwrap();
+        check_array_eq::<StringArray>((&result).try_into().unwrap(), &[None, Some("xc")]);
+
+        let result = expr
+            .eval_scalar_scalar(ScalarRefImpl::String("x"), ScalarRefImpl::String("y"))
+            .unwrap();
+        assert_eq!(result, ScalarImpl::String("xy".to_string()));
+    }
+
+    #[test]
+    fn test_eval_with_scalar_type_mismatch() {
+        let expr =
+            BinaryExpression::<I32Array, I32Array, BoolArray, _>::new(cmp_lt::<i32, i32, i32>);
+        let array: ArrayImpl = I32Array::from_slice(&[Some(1), Some(2)]).into();
+
+        let result = expr
+            .eval_array_scalar(&array, ScalarRefImpl::I32(2))
+            .unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[Some(true), Some(false)]);
+
+        assert!(expr
+            .eval_array_scalar(&array, ScalarRefImpl::I64(2))
+            .is_err());
+        assert!(expr
+            .eval_scalar_array(ScalarRefImpl::Bool(true), &array)
+            .is_err());
+        assert!(expr
+            .eval_scalar_scalar(ScalarRefImpl::I32(1), ScalarRefImpl::String("2"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let expr =
+            BinaryExpression::<I32Array, I32Array, BoolArray, _>::new(cmp_lt::<i32, i32, i32>);
+        let i32s: ArrayImpl = I32Array::from_slice(&[Some(1), Some(2)]).into();
+        let i64s: ArrayImpl = I64Array::from_slice(&[Some(1), Some(2)]).into();
+
+        assert!(matches!(
+            expr.eval(&i32s, &i32s.slice(0, 1)),
+            Err(EvalError::LengthMismatch { left: 2, right: 1 })
+        ));
+        assert!(matches!(
+            expr.eval(&i32s, &i64s),
+            Err(EvalError::TypeMismatch {
+                expected: DataType::Int32,
+                actual: DataType::Int64
+            })
+        ));
+        assert!(matches!(
+            expr.eval_expr(&[&i32s, &i32s, &i32s]),
+            Err(EvalError::ArityMismatch {
+                expected: 2,
+                actual: 3
+            })
+        ));
+    }
+}
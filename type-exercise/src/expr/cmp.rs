@@ -1,75 +1,176 @@
-use crate::scalar::Scalar;
+use std::cmp::Ordering;
+
+use super::{BinaryExpression, FunctionRegistry, TernaryExpression};
+use crate::array::*;
+use crate::datatype::DataType;
+use crate::macros::{for_all_comparable_type_pairs, for_all_types};
+use crate::scalar::{Scalar, Widen};
+
+/// The order used by comparisons. It is total, even for `f64`: NaN equals NaN and is greater
+/// than any other value, and `-0.0` equals `0.0`.
+pub trait TotalOrd {
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_total_ord_for_ord {
+    ($($t:ty),*) => {
+        $(
+            impl TotalOrd for $t {
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    self.cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_total_ord_for_ord! { i32, i64, bool, &str }
+
+impl TotalOrd for f64 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self.is_nan(), other.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.partial_cmp(other).unwrap(),
+        }
+    }
+}
+
+/// Compare two values after converting both to `C`.
+fn cmp<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> Ordering
+where
+    for<'a> I1::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> C::RefType<'a>: TotalOrd,
+{
+    let c1 = C::upcast_ref(I1::upcast_ref(i1).widen());
+    let c2 = C::upcast_ref(I2::upcast_ref(i2).widen());
+    c1.total_cmp(&c2)
+}
 
 pub fn cmp_lt<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> bool
 where
-    for<'a> I1::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> I2::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a, 'b> C::RefType<'a>: PartialOrd<C::RefType<'b>>,
+    for<'a> I1::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> C::RefType<'a>: TotalOrd,
 {
-    let c1 = i1.into();
-    let c2 = i2.into();
-    c1 < c2
+    cmp::<I1, I2, C>(i1, i2).is_lt()
 }
 
 pub fn cmp_le<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> bool
 where
-    for<'a> I1::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> I2::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a, 'b> C::RefType<'a>: PartialOrd<C::RefType<'b>>,
+    for<'a> I1::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> C::RefType<'a>: TotalOrd,
 {
-    let c1 = i1.into();
-    let c2 = i2.into();
-    c1 <= c2
+    cmp::<I1, I2, C>(i1, i2).is_le()
 }
 
 pub fn cmp_gt<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> bool
 where
-    for<'a> I1::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> I2::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a, 'b> C::RefType<'a>: PartialOrd<C::RefType<'b>>,
+    for<'a> I1::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> C::RefType<'a>: TotalOrd,
 {
-    let c1 = i1.into();
-    let c2 = i2.into();
-    c1 >= c2
+    cmp::<I1, I2, C>(i1, i2).is_gt()
 }
 
 pub fn cmp_ge<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> bool
 where
-    for<'a> I1::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> I2::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a, 'b> C::RefType<'a>: PartialOrd<C::RefType<'b>>,
+    for<'a> I1::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> C::RefType<'a>: TotalOrd,
 {
-    let c1 = i1.into();
-    let c2 = i2.into();
-    c1 >= c2
+    cmp::<I1, I2, C>(i1, i2).is_ge()
 }
 
 pub fn cmp_eq<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> bool
 where
-    for<'a> I1::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> I2::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> C::RefType<'a>: Eq,
+    for<'a> I1::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> C::RefType<'a>: TotalOrd,
 {
-    let c1 = I1::upcast_ref(i1).into();
-    let c2 = I2::upcast_ref(i2).into();
-    c1 == c2
+    cmp::<I1, I2, C>(i1, i2).is_eq()
 }
 
 pub fn cmp_ne<I1: Scalar, I2: Scalar, C: Scalar>(i1: I1::RefType<'_>, i2: I2::RefType<'_>) -> bool
 where
-    for<'a> I1::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> I2::RefType<'a>: Into<C::RefType<'a>>,
-    for<'a> C::RefType<'a>: Eq,
+    for<'a> I1::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> I2::RefType<'a>: Widen<C::RefType<'a>>,
+    for<'a> C::RefType<'a>: TotalOrd,
+{
+    cmp::<I1, I2, C>(i1, i2).is_ne()
+}
+
+/// Check if `low <= i <= high`.
+pub fn between<T: Scalar>(i: T::RefType<'_>, low: T::RefType<'_>, high: T::RefType<'_>) -> bool
+where
+    for<'a> T::RefType<'a>: TotalOrd,
 {
-    let c1 = I1::upcast_ref(i1).into();
-    let c2 = I2::upcast_ref(i2).into();
-    c1 != c2
+    cmp_le::<T, T, T>(low, i) && cmp_le::<T, T, T>(i, high)
+}
+
+macro_rules! impl_register_cmp_functions {
+    ($({ $Name1:ident, $Array1:ident, $Owned1:ty, $Name2:ident, $Array2:ident, $Owned2:ty, $NameC:ident, $ArrayC:ident, $OwnedC:ty }),*) => {
+        /// Register comparisons between values of comparable types.
+        fn register_binary_cmp_functions(registry: &mut FunctionRegistry) {
+            $(
+                let args = [DataType::$Name1, DataType::$Name2];
+                registry.register("cmp_lt", &args, DataType::Bool, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, BoolArray, _>::new(cmp_lt::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("cmp_le", &args, DataType::Bool, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, BoolArray, _>::new(cmp_le::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("cmp_gt", &args, DataType::Bool, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, BoolArray, _>::new(cmp_gt::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("cmp_ge", &args, DataType::Bool, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, BoolArray, _>::new(cmp_ge::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("cmp_eq", &args, DataType::Bool, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, BoolArray, _>::new(cmp_eq::<$Owned1, $Owned2, $OwnedC>))
+                });
+                registry.register("cmp_ne", &args, DataType::Bool, || {
+                    Box::new(BinaryExpression::<$Array1, $Array2, BoolArray, _>::new(cmp_ne::<$Owned1, $Owned2, $OwnedC>))
+                });
+            )*
+        }
+    };
+}
+
+for_all_comparable_type_pairs! { impl_register_cmp_functions }
+
+macro_rules! impl_register_between_functions {
+    ($({ $Name:ident, $Variant:ident, $Array:ident, $ArrayBuilder:ident, $Owned:ty, $Ref:ty }),*) => {
+        /// Register `between` on three values of the same type.
+        fn register_between_functions(registry: &mut FunctionRegistry) {
+            $(
+                registry.register("between", &[DataType::$Name; 3], DataType::Bool, || {
+                    Box::new(TernaryExpression::<$Array, $Array, $Array, BoolArray, _>::new(between::<$Owned>))
+                });
+            )*
+        }
+    };
+}
+
+for_all_types! { impl_register_between_functions }
+
+/// Register comparisons.
+pub(super) fn register_cmp_functions(registry: &mut FunctionRegistry) {
+    register_binary_cmp_functions(registry);
+    register_between_functions(registry);
 }
 
 #[cfg(test)]
 mod test {
+    use std::cmp::Ordering;
+
     use crate::array::*;
+    use crate::datatype::DataType;
     use crate::expr::*;
+    use crate::scalar::{ScalarImpl, ScalarRefImpl};
     use crate::test_util::*;
 
     #[test]
@@ -99,4 +200,184 @@ mod test {
             .unwrap();
         check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[Some(true), None, None]);
     }
+
+    #[test]
+    fn test_nan_ordering() {
+        let nan = f64::NAN;
+        assert!(cmp_eq::<f64, f64, f64>(nan, nan));
+        assert!(cmp_lt::<f64, f64, f64>(f64::INFINITY, nan));
+        assert!(cmp_lt::<i64, f64, f64>(i64::MAX, nan));
+        assert!(!cmp_ge::<f64, f64, f64>(1.0, nan));
+        assert!(cmp_ne::<f64, i32, f64>(nan, 0));
+        assert!(cmp_eq::<f64, f64, f64>(-0.0, 0.0));
+        assert!(cmp_lt::<f64, f64, f64>(f64::NEG_INFINITY, -0.0));
+        assert!(between::<f64>(nan, 0.0, nan));
+    }
+
+    /// The interesting values of each type, including null.
+    fn conformance_values() -> Vec<Vec<Option<ScalarImpl>>> {
+        fn values<T: Into<ScalarImpl>>(values: Vec<T>) -> Vec<Option<ScalarImpl>> {
+            values
+                .into_iter()
+                .map(|value| Some(value.into()))
+                .chain([None])
+                .collect()
+        }
+        vec![
+            values(vec![i32::MIN, -1, 0, 1, i32::MAX]),
+            values(vec![i64::MIN, -1, 0, 1, i32::MAX as i64 + 1, i64::MAX]),
+            values(vec![
+                f64::NEG_INFINITY,
+                f64::MIN,
+                -1.5,
+                -0.0,
+                0.0,
+                1.0,
+                f64::MAX,
+                f64::INFINITY,
+                f64::NAN,
+            ]),
+            values(vec![false, true]),
+            values(["", "a", "ab", "b", "B", "é"].map(String::from).to_vec()),
+        ]
+    }
+
+    /// Compare two non-null scalars as SQL does, or `None` if they are not comparable.
+    fn reference_cmp(a: &ScalarImpl, b: &ScalarImpl) -> Option<Ordering> {
+        fn as_f64(value: &ScalarImpl) -> Option<f64> {
+            match value {
+                ScalarImpl::I32(v) => Some(*v as f64),
+                ScalarImpl::I64(v) => Some(*v as f64),
+                ScalarImpl::F64(v) => Some(*v),
+                _ => None,
+            }
+        }
+        fn as_i64(value: &ScalarImpl) -> Option<i64> {
+            match value {
+                ScalarImpl::I32(v) => Some(*v as i64),
+                ScalarImpl::I64(v) => Some(*v),
+                _ => None,
+            }
+        }
+        match (a, b) {
+            (ScalarImpl::Bool(a), ScalarImpl::Bool(b)) => Some(a.cmp(b)),
+            (ScalarImpl::String(a), ScalarImpl::String(b)) => Some(a.cmp(b)),
+            (ScalarImpl::F64(_), _) | (_, ScalarImpl::F64(_)) => {
+                let (a, b) = (as_f64(a)?, as_f64(b)?);
+                Some(match (a.is_nan(), b.is_nan()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => a.partial_cmp(&b).unwrap(),
+                })
+            }
+            _ => Some(as_i64(a)?.cmp(&as_i64(b)?)),
+        }
+    }
+
+    fn build_array(data_type: DataType, values: &[Option<ScalarImpl>]) -> ArrayImpl {
+        let mut builder = ArrayBuilderImpl::with_capacity(data_type, values.len());
+        for value in values {
+            builder.push(value.as_ref().map(ScalarImpl::as_scalar_ref));
+        }
+        builder.finish()
+    }
+
+    /// Every registered comparison, on every pair of comparable types and every pair of their
+    /// interesting values, agrees with [`reference_cmp`].
+    #[test]
+    fn test_cmp_conformance() {
+        let registry = FunctionRegistry::with_builtins();
+        let comparisons = [
+            ("cmp_lt", Ordering::is_lt as fn(_) -> _),
+            ("cmp_le", Ordering::is_le),
+            ("cmp_gt", Ordering::is_gt),
+            ("cmp_ge", Ordering::is_ge),
+            ("cmp_eq", Ordering::is_eq),
+            ("cmp_ne", Ordering::is_ne),
+        ];
+        let all_values = conformance_values();
+        for left_values in &all_values {
+            for right_values in &all_values {
+                let left_type = left_values[0].as_ref().unwrap().data_type();
+                let right_type = right_values[0].as_ref().unwrap().data_type();
+                if left_type.common_type(right_type).is_none() {
+                    continue;
+                }
+                let (left, right): (Vec<_>, Vec<_>) = left_values
+                    .iter()
+                    .flat_map(|a| right_values.iter().map(move |b| (a.clone(), b.clone())))
+                    .unzip();
+                let left_array = build_array(left_type, &left);
+                let right_array = build_array(right_type, &right);
+
+                for (name, expected) in comparisons {
+                    let result = registry
+                        .build(name, &[left_type, right_type])
+                        .unwrap()
+                        .eval_expr(&[&left_array, &right_array])
+                        .unwrap();
+                    for (idx, (a, b)) in left.iter().zip(&right).enumerate() {
+                        let expected = match (a, b) {
+                            (Some(a), Some(b)) => Some(expected(reference_cmp(a, b).unwrap())),
+                            _ => None,
+                        };
+                        assert_eq!(
+                            result.get(idx),
+                            expected.map(ScalarRefImpl::Bool),
+                            "{name}({a:?}, {b:?})"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixed_type_registry() {
+        let registry = FunctionRegistry::with_builtins();
+        let types = [
+            DataType::Int32,
+            DataType::Int64,
+            DataType::Float64,
+            DataType::Bool,
+            DataType::String,
+        ];
+        for left in types {
+            for right in types {
+                let func = registry.lookup("cmp_eq", &[left, right]);
+                assert_eq!(
+                    func.is_ok(),
+                    left.common_type(right).is_some(),
+                    "cmp_eq({left}, {right})"
+                );
+            }
+        }
+
+        let result = registry
+            .build("cmp_lt", &[DataType::Int32, DataType::Int64])
+            .unwrap()
+            .eval_expr(&[
+                &I32Array::from_slice(&[Some(1), Some(i32::MAX), None]).into(),
+                &I64Array::from_slice(&[Some(2), Some(i32::MAX as i64 + 1), Some(0)]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(true), None],
+        );
+
+        let result = registry
+            .build("cmp_eq", &[DataType::Int64, DataType::Float64])
+            .unwrap()
+            .eval_expr(&[
+                &I64Array::from_slice(&[Some(1), Some(1), Some(3)]).into(),
+                &F64Array::from_slice(&[Some(1.0), Some(1.5), Some(f64::NAN)]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(false), Some(false)],
+        );
+    }
 }
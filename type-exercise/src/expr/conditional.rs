@@ -0,0 +1,200 @@
+use super::{
+    cmp_eq, cmp_ne, BinaryExpression, FunctionRegistry, TotalOrd, UnaryExpression,
+    VariadicExpression,
+};
+use crate::array::*;
+use crate::datatype::DataType;
+use crate::macros::for_all_types;
+use crate::scalar::{Scalar, ScalarRef};
+
+/// The first non-null argument, or null if all arguments are null.
+pub fn coalesce<T: Scalar>(args: &[Option<T::RefType<'_>>]) -> Option<T> {
+    args.iter().flatten().next().map(ScalarRef::to_owned_scalar)
+}
+
+/// `i IS NULL`.
+pub fn is_null<T: Scalar>(i: Option<T::RefType<'_>>) -> Option<bool> {
+    Some(i.is_none())
+}
+
+/// `i IS NOT NULL`.
+pub fn is_not_null<T: Scalar>(i: Option<T::RefType<'_>>) -> Option<bool> {
+    Some(i.is_some())
+}
+
+/// `i1 IS DISTINCT FROM i2`, which is `i1 <> i2` with nulls compared as equal to each other and
+/// unequal to anything else. Never returns null.
+pub fn is_distinct_from<T: Scalar>(
+    i1: Option<T::RefType<'_>>,
+    i2: Option<T::RefType<'_>>,
+) -> Option<bool>
+where
+    for<'a> T::RefType<'a>: TotalOrd,
+{
+    match (i1, i2) {
+        (Some(i1), Some(i2)) => Some(cmp_ne::<T, T, T>(i1, i2)),
+        (i1, i2) => Some(i1.is_some() != i2.is_some()),
+    }
+}
+
+/// `i1 IS NOT DISTINCT FROM i2`.
+pub fn is_not_distinct_from<T: Scalar>(
+    i1: Option<T::RefType<'_>>,
+    i2: Option<T::RefType<'_>>,
+) -> Option<bool>
+where
+    for<'a> T::RefType<'a>: TotalOrd,
+{
+    is_distinct_from::<T>(i1, i2).map(|distinct| !distinct)
+}
+
+/// `NULLIF(i1, i2)`: null if `i1 = i2`, otherwise `i1`.
+pub fn nullif<T: Scalar>(i1: Option<T::RefType<'_>>, i2: Option<T::RefType<'_>>) -> Option<T>
+where
+    for<'a> T::RefType<'a>: TotalOrd,
+{
+    let i1 = i1?;
+    match i2 {
+        Some(i2) if cmp_eq::<T, T, T>(i1, i2) => None,
+        _ => Some(i1.to_owned_scalar()),
+    }
+}
+
+macro_rules! impl_register_conditional_functions {
+    ($({ $Name:ident, $Variant:ident, $Array:ident, $ArrayBuilder:ident, $Owned:ty, $Ref:ty }),*) => {
+        /// Register conditional and null-handling functions on all types.
+        pub(super) fn register_conditional_functions(registry: &mut FunctionRegistry) {
+            $(
+                let args = [DataType::$Name];
+                registry.register("is_null", &args, DataType::Bool, || {
+                    Box::new(UnaryExpression::<$Array, BoolArray, _, _>::new_null_aware(is_null::<$Owned>))
+                });
+                registry.register("is_not_null", &args, DataType::Bool, || {
+                    Box::new(UnaryExpression::<$Array, BoolArray, _, _>::new_null_aware(is_not_null::<$Owned>))
+                });
+
+                let args = [DataType::$Name, DataType::$Name];
+                registry.register("is_distinct_from", &args, DataType::Bool, || {
+                    Box::new(BinaryExpression::<$Array, $Array, BoolArray, _, _>::new_null_aware(is_distinct_from::<$Owned>))
+                });
+                registry.register("is_not_distinct_from", &args, DataType::Bool, || {
+                    Box::new(BinaryExpression::<$Array, $Array, BoolArray, _, _>::new_null_aware(is_not_distinct_from::<$Owned>))
+                });
+                registry.register("nullif", &args, DataType::$Name, || {
+                    Box::new(BinaryExpression::<$Array, $Array, $Array, _, _>::new_null_aware(nullif::<$Owned>))
+                });
+                registry.register_variadic("coalesce", &[DataType::$Name], DataType::$Name, DataType::$Name, || {
+                    Box::new(VariadicExpression::<$Array, $Array, _, _>::new_null_aware(coalesce::<$Owned>))
+                });
+            )*
+        }
+    };
+}
+
+for_all_types! { impl_register_conditional_functions }
+
+#[cfg(test)]
+mod test {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::test_util::*;
+
+    #[test]
+    fn test_is_null() {
+        let registry = FunctionRegistry::with_builtins();
+        let input: ArrayImpl = StringArray::from_slice(&[Some("a"), None, Some("")]).into();
+        let result = registry
+            .build("is_null", &[DataType::String])
+            .unwrap()
+            .eval_expr(&[&input])
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(false), Some(true), Some(false)],
+        );
+        let result = registry
+            .build("is_not_null", &[DataType::String])
+            .unwrap()
+            .eval_expr(&[&input])
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(false), Some(true)],
+        );
+    }
+
+    #[test]
+    fn test_is_distinct_from() {
+        // Pairs of (1, 2, NULL) in row-major order.
+        let values = [Some(1), Some(2), None];
+        let result = eval_on_all_pairs::<I32Array>("is_distinct_from", &values);
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[
+                Some(false),
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(false),
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(false),
+            ],
+        );
+        let result = eval_on_all_pairs::<I32Array>("is_not_distinct_from", &values);
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[
+                Some(true),
+                Some(false),
+                Some(false),
+                Some(false),
+                Some(true),
+                Some(false),
+                Some(false),
+                Some(false),
+                Some(true),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_nullif() {
+        let result = eval_on_all_pairs::<I32Array>("nullif", &[Some(1), Some(2), None]);
+        check_array_eq::<I32Array>(
+            (&result).try_into().unwrap(),
+            &[
+                None,
+                Some(1),
+                Some(1),
+                Some(2),
+                None,
+                Some(2),
+                None,
+                None,
+                None,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_coalesce_of_two() {
+        let result = eval_on_all_pairs::<I32Array>("coalesce", &[Some(1), Some(2), None]);
+        check_array_eq::<I32Array>(
+            (&result).try_into().unwrap(),
+            &[
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(1),
+                Some(2),
+                None,
+            ],
+        );
+    }
+}
@@ -0,0 +1,359 @@
+use super::{Expression, FunctionRegistry};
+use crate::array::{Array, ArrayImpl, BoolArray};
+use crate::datatype::DataType;
+use crate::scalar::ScalarRefImpl;
+use crate::{EvalError, TypeMismatch};
+
+/// SQL `AND`: false if either side is false, otherwise null if either side is null.
+pub fn kleene_and(i1: Option<bool>, i2: Option<bool>) -> Option<bool> {
+    match (i1, i2) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+/// SQL `OR`: true if either side is true, otherwise null if either side is null.
+pub fn kleene_or(i1: Option<bool>, i2: Option<bool>) -> Option<bool> {
+    match (i1, i2) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+/// SQL `XOR`: null if either side is null.
+pub fn kleene_xor(i1: Option<bool>, i2: Option<bool>) -> Option<bool> {
+    Some(i1? ^ i2?)
+}
+
+/// SQL `NOT`: null if the argument is null.
+pub fn kleene_not(i: Option<bool>) -> Option<bool> {
+    i.map(|i| !i)
+}
+
+/// 64 (or 32) booleans as a pair of `(values, validity)` words. Values of null slots are zero.
+type Words = (usize, usize);
+
+fn and_words((a, a_valid): Words, (b, b_valid): Words) -> Words {
+    let is_false = (a_valid & !a) | (b_valid & !b);
+    (a & b, (a & b) | is_false)
+}
+
+fn or_words((a, a_valid): Words, (b, b_valid): Words) -> Words {
+    (a | b, (a | b) | (a_valid & b_valid))
+}
+
+fn xor_words((a, a_valid): Words, (b, b_valid): Words) -> Words {
+    let valid = a_valid & b_valid;
+    ((a ^ b) & valid, valid)
+}
+
+/// The words of a scalar repeated in every slot.
+fn scalar_words(i: Option<bool>) -> Words {
+    match i {
+        Some(true) => (usize::MAX, usize::MAX),
+        Some(false) => (0, usize::MAX),
+        None => (0, 0),
+    }
+}
+
+fn array_words(i: &BoolArray) -> impl Iterator<Item = Words> + '_ {
+    i.value_words().zip(i.validity_words())
+}
+
+fn collect_words(words: impl Iterator<Item = Words>, len: usize) -> BoolArray {
+    let (values, validity) = words.unzip();
+    BoolArray::from_words(values, validity, len)
+}
+
+fn try_into_bool(i: Option<ScalarRefImpl<'_>>) -> Result<Option<bool>, TypeMismatch> {
+    i.map(TryInto::try_into).transpose()
+}
+
+/// A binary logical connective under SQL three-valued logic, evaluated a machine word of
+/// booleans at a time.
+pub struct LogicalExpression {
+    kernel: fn(Words, Words) -> Words,
+}
+
+impl LogicalExpression {
+    pub fn and() -> Self {
+        Self { kernel: and_words }
+    }
+
+    pub fn or() -> Self {
+        Self { kernel: or_words }
+    }
+
+    pub fn xor() -> Self {
+        Self { kernel: xor_words }
+    }
+
+    pub fn eval(&self, i1: &ArrayImpl, i2: &ArrayImpl) -> Result<ArrayImpl, EvalError> {
+        Ok(self.eval_array(i1.try_into()?, i2.try_into()?)?.into())
+    }
+
+    /// Evaluate the expression on two arrays of booleans.
+    pub fn eval_array(&self, i1: &BoolArray, i2: &BoolArray) -> Result<BoolArray, EvalError> {
+        if i1.len() != i2.len() {
+            return Err(EvalError::LengthMismatch {
+                left: i1.len(),
+                right: i2.len(),
+            });
+        }
+        let words = array_words(i1)
+            .zip(array_words(i2))
+            .map(|(a, b)| (self.kernel)(a, b));
+        Ok(collect_words(words, i1.len()))
+    }
+
+    /// Evaluate the expression on an array and a nullable scalar, which is used as the right
+    /// operand of every row.
+    pub fn eval_array_scalar(
+        &self,
+        i1: &ArrayImpl,
+        i2: Option<ScalarRefImpl<'_>>,
+    ) -> Result<ArrayImpl, EvalError> {
+        let i1: &BoolArray = i1.try_into()?;
+        let i2 = scalar_words(try_into_bool(i2)?);
+        let words = array_words(i1).map(|a| (self.kernel)(a, i2));
+        Ok(collect_words(words, i1.len()).into())
+    }
+
+    /// Evaluate the expression on a nullable scalar and an array, which is used as the left
+    /// operand of every row.
+    pub fn eval_scalar_array(
+        &self,
+        i1: Option<ScalarRefImpl<'_>>,
+        i2: &ArrayImpl,
+    ) -> Result<ArrayImpl, EvalError> {
+        let i1 = scalar_words(try_into_bool(i1)?);
+        let i2: &BoolArray = i2.try_into()?;
+        let words = array_words(i2).map(|b| (self.kernel)(i1, b));
+        Ok(collect_words(words, i2.len()).into())
+    }
+}
+
+impl Expression for LogicalExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl, EvalError> {
+        if data.len() != 2 {
+            return Err(EvalError::ArityMismatch {
+                expected: 2,
+                actual: data.len(),
+            });
+        }
+        self.eval(data[0], data[1])
+    }
+}
+
+/// SQL `NOT`, evaluated a machine word of booleans at a time.
+pub struct NotExpression;
+
+impl NotExpression {
+    pub fn eval(&self, i: &ArrayImpl) -> Result<ArrayImpl, EvalError> {
+        Ok(self.eval_array(i.try_into()?).into())
+    }
+
+    /// Evaluate the expression on an array of booleans.
+    pub fn eval_array(&self, i: &BoolArray) -> BoolArray {
+        let words = array_words(i).map(|(a, a_valid)| (!a & a_valid, a_valid));
+        collect_words(words, i.len())
+    }
+}
+
+impl Expression for NotExpression {
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl, EvalError> {
+        if data.len() != 1 {
+            return Err(EvalError::ArityMismatch {
+                expected: 1,
+                actual: data.len(),
+            });
+        }
+        self.eval(data[0])
+    }
+}
+
+/// Register logical connectives.
+pub(super) fn register_logical_functions(registry: &mut FunctionRegistry) {
+    let args = [DataType::Bool, DataType::Bool];
+    registry.register("and", &args, DataType::Bool, || {
+        Box::new(LogicalExpression::and())
+    });
+    registry.register("or", &args, DataType::Bool, || {
+        Box::new(LogicalExpression::or())
+    });
+    registry.register("xor", &args, DataType::Bool, || {
+        Box::new(LogicalExpression::xor())
+    });
+    registry.register("not", &[DataType::Bool], DataType::Bool, || {
+        Box::new(NotExpression)
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::scalar::{ScalarImpl, ScalarRefImpl};
+    use crate::test_util::*;
+    use crate::EvalError;
+
+    /// TRUE, FALSE and NULL, whose pairs in row-major order make up the truth tables below.
+    const VALUES: [Option<bool>; 3] = [Some(true), Some(false), None];
+
+    #[test]
+    fn test_and() {
+        let result = eval_on_all_pairs::<BoolArray>("and", &VALUES);
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[
+                Some(true),
+                Some(false),
+                None,
+                Some(false),
+                Some(false),
+                Some(false),
+                None,
+                Some(false),
+                None,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_or() {
+        let result = eval_on_all_pairs::<BoolArray>("or", &VALUES);
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(false),
+                None,
+                Some(true),
+                None,
+                None,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_xor() {
+        let result = eval_on_all_pairs::<BoolArray>("xor", &VALUES);
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[
+                Some(false),
+                Some(true),
+                None,
+                Some(true),
+                Some(false),
+                None,
+                None,
+                None,
+                None,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_not() {
+        let result = FunctionRegistry::with_builtins()
+            .build("not", &[DataType::Bool])
+            .unwrap()
+            .eval_expr(&[&BoolArray::from_slice(&VALUES).into()])
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(false), Some(true), None],
+        );
+    }
+
+    #[test]
+    fn test_with_scalars() {
+        let array: ArrayImpl = BoolArray::from_slice(&VALUES).into();
+        let expr = LogicalExpression::and();
+        let result = expr
+            .eval_array_scalar(&array, Some(ScalarRefImpl::Bool(true)))
+            .unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &VALUES);
+        let result = expr.eval_scalar_array(None, &array).unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[None, Some(false), None]);
+
+        let expr = LogicalExpression::or();
+        let result = expr.eval_array_scalar(&array, None).unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[Some(true), None, None]);
+        let result = expr
+            .eval_scalar_array(Some(ScalarRefImpl::Bool(true)), &array)
+            .unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[Some(true); 3]);
+
+        assert!(matches!(
+            expr.eval_array_scalar(&array, Some(ScalarRefImpl::I32(1))),
+            Err(EvalError::TypeMismatch {
+                expected: DataType::Bool,
+                actual: DataType::Int32
+            })
+        ));
+    }
+
+    #[test]
+    fn test_null_aware_scalars() {
+        let expr =
+            BinaryExpression::<BoolArray, BoolArray, BoolArray, _, _>::new_null_aware(kleene_or);
+        let result = expr
+            .eval_array_scalar(
+                &BoolArray::from_slice(&VALUES).into(),
+                ScalarRefImpl::Bool(true),
+            )
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(true), Some(true)],
+        );
+        assert_eq!(
+            expr.eval_scalar_scalar(None, Some(ScalarRefImpl::Bool(false)))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            expr.eval_scalar_scalar(None, Some(ScalarRefImpl::Bool(true)))
+                .unwrap(),
+            Some(ScalarImpl::Bool(true))
+        );
+    }
+
+    fn bools() -> impl Strategy<Value = Vec<Option<bool>>> {
+        prop::collection::vec(any::<Option<bool>>(), 0..300)
+    }
+
+    proptest! {
+        /// The word-at-a-time kernels agree with the row-at-a-time definitions, also on slices
+        /// that do not start at a word boundary.
+        #[test]
+        fn test_kernels_match_rows(a in bools(), b in bools(), offset in 0..70usize) {
+            let offset = offset.min(a.len());
+            let len = (a.len() - offset).min(b.len());
+            let i1 = BoolArray::from_slice(&a).slice(offset, len);
+            let i2 = BoolArray::from_slice(&b[..len]);
+            for (expr, func) in [
+                (LogicalExpression::and(), kleene_and as fn(_, _) -> _),
+                (LogicalExpression::or(), kleene_or),
+                (LogicalExpression::xor(), kleene_xor),
+            ] {
+                let result = expr.eval_array(&i1, &i2).unwrap();
+                let expected: Vec<_> = i1.iter().zip(i2.iter()).map(|(a, b)| func(a, b)).collect();
+                check_array_eq(&result, &expected);
+            }
+            let result = NotExpression.eval_array(&i1);
+            let expected: Vec<_> = i1.iter().map(kleene_not).collect();
+            check_array_eq(&result, &expected);
+        }
+    }
+}
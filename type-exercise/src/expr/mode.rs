@@ -0,0 +1,317 @@
+use crate::array::{Array, ArrayBuilder, StringArray, StringWriter};
+use crate::scalar::Scalar;
+use crate::EvalError;
+
+/// Any null argument makes the result null. The function is only called on non-null
+/// arguments, and always produces a non-null result.
+pub struct Strict;
+
+/// The function is called on every row with nullable arguments, and decides if the result is
+/// null.
+pub struct NullAware;
+
+/// Like [`Strict`], but the function may fail, which fails the whole evaluation.
+pub struct Checked;
+
+/// Like [`Strict`], but the function may fail, which makes the result of that row null.
+pub struct NullOnError;
+
+/// Like [`Strict`], but the function writes a string result through a [`StringWriter`] instead
+/// of returning it, so no `String` is allocated per row.
+pub struct Writer;
+
+/// How a [`UnaryExpression`](super::UnaryExpression) calls its function on one row.
+pub trait UnaryMode<I: Array, O: Array, F> {
+    fn eval_row(
+        func: &F,
+        a: Option<I::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError>;
+}
+
+impl<I: Array, O: Array, F> UnaryMode<I, O, F> for Strict
+where
+    F: Fn(I::RefItem<'_>) -> O::OwnedItem,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        match a {
+            Some(a) => builder.push(Some(func(a).as_scalar_ref())),
+            None => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+impl<I: Array, O: Array, F> UnaryMode<I, O, F> for NullAware
+where
+    F: Fn(Option<I::RefItem<'_>>) -> Option<O::OwnedItem>,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        builder.push(func(a).as_ref().map(Scalar::as_scalar_ref));
+        Ok(())
+    }
+}
+
+impl<I: Array, O: Array, F> UnaryMode<I, O, F> for Checked
+where
+    F: Fn(I::RefItem<'_>) -> Result<O::OwnedItem, EvalError>,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        match a {
+            Some(a) => builder.push(Some(func(a)?.as_scalar_ref())),
+            None => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+impl<I: Array, O: Array, F> UnaryMode<I, O, F> for NullOnError
+where
+    F: Fn(I::RefItem<'_>) -> Result<O::OwnedItem, EvalError>,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        let result = a.and_then(|a| func(a).ok());
+        builder.push(result.as_ref().map(Scalar::as_scalar_ref));
+        Ok(())
+    }
+}
+
+impl<I: Array, F> UnaryMode<I, StringArray, F> for Writer
+where
+    F: Fn(I::RefItem<'_>, &mut StringWriter<'_>),
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I::RefItem<'_>>,
+        builder: &mut <StringArray as Array>::Builder,
+    ) -> Result<(), EvalError> {
+        match a {
+            Some(a) => builder.push_with(|writer| func(a, writer)),
+            None => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+/// How a [`BinaryExpression`](super::BinaryExpression) calls its function on one row.
+pub trait BinaryMode<I1: Array, I2: Array, O: Array, F> {
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError>;
+}
+
+impl<I1: Array, I2: Array, O: Array, F> BinaryMode<I1, I2, O, F> for Strict
+where
+    F: Fn(I1::RefItem<'_>, I2::RefItem<'_>) -> O::OwnedItem,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        match (a, b) {
+            (Some(a), Some(b)) => builder.push(Some(func(a, b).as_scalar_ref())),
+            _ => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+impl<I1: Array, I2: Array, O: Array, F> BinaryMode<I1, I2, O, F> for NullAware
+where
+    F: Fn(Option<I1::RefItem<'_>>, Option<I2::RefItem<'_>>) -> Option<O::OwnedItem>,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        builder.push(func(a, b).as_ref().map(Scalar::as_scalar_ref));
+        Ok(())
+    }
+}
+
+impl<I1: Array, I2: Array, O: Array, F> BinaryMode<I1, I2, O, F> for Checked
+where
+    F: Fn(I1::RefItem<'_>, I2::RefItem<'_>) -> Result<O::OwnedItem, EvalError>,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        match (a, b) {
+            (Some(a), Some(b)) => builder.push(Some(func(a, b)?.as_scalar_ref())),
+            _ => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+impl<I1: Array, I2: Array, O: Array, F> BinaryMode<I1, I2, O, F> for NullOnError
+where
+    F: Fn(I1::RefItem<'_>, I2::RefItem<'_>) -> Result<O::OwnedItem, EvalError>,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        let result = match (a, b) {
+            (Some(a), Some(b)) => func(a, b).ok(),
+            _ => None,
+        };
+        builder.push(result.as_ref().map(Scalar::as_scalar_ref));
+        Ok(())
+    }
+}
+
+impl<I1: Array, I2: Array, F> BinaryMode<I1, I2, StringArray, F> for Writer
+where
+    F: Fn(I1::RefItem<'_>, I2::RefItem<'_>, &mut StringWriter<'_>),
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        builder: &mut <StringArray as Array>::Builder,
+    ) -> Result<(), EvalError> {
+        match (a, b) {
+            (Some(a), Some(b)) => builder.push_with(|writer| func(a, b, writer)),
+            _ => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+/// How a [`TernaryExpression`](super::TernaryExpression) calls its function on one row.
+pub trait TernaryMode<I1: Array, I2: Array, I3: Array, O: Array, F> {
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        c: Option<I3::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError>;
+}
+
+impl<I1: Array, I2: Array, I3: Array, O: Array, F> TernaryMode<I1, I2, I3, O, F> for Strict
+where
+    F: Fn(I1::RefItem<'_>, I2::RefItem<'_>, I3::RefItem<'_>) -> O::OwnedItem,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        c: Option<I3::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        match (a, b, c) {
+            (Some(a), Some(b), Some(c)) => builder.push(Some(func(a, b, c).as_scalar_ref())),
+            _ => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+impl<I1: Array, I2: Array, I3: Array, O: Array, F> TernaryMode<I1, I2, I3, O, F> for NullAware
+where
+    F: Fn(
+        Option<I1::RefItem<'_>>,
+        Option<I2::RefItem<'_>>,
+        Option<I3::RefItem<'_>>,
+    ) -> Option<O::OwnedItem>,
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        c: Option<I3::RefItem<'_>>,
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        builder.push(func(a, b, c).as_ref().map(Scalar::as_scalar_ref));
+        Ok(())
+    }
+}
+
+impl<I1: Array, I2: Array, I3: Array, F> TernaryMode<I1, I2, I3, StringArray, F> for Writer
+where
+    F: Fn(I1::RefItem<'_>, I2::RefItem<'_>, I3::RefItem<'_>, &mut StringWriter<'_>),
+{
+    fn eval_row(
+        func: &F,
+        a: Option<I1::RefItem<'_>>,
+        b: Option<I2::RefItem<'_>>,
+        c: Option<I3::RefItem<'_>>,
+        builder: &mut <StringArray as Array>::Builder,
+    ) -> Result<(), EvalError> {
+        match (a, b, c) {
+            (Some(a), Some(b), Some(c)) => builder.push_with(|writer| func(a, b, c, writer)),
+            _ => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+/// How a [`VariadicExpression`](super::VariadicExpression) calls its function on one row.
+pub trait VariadicMode<I: Array, O: Array, F> {
+    fn eval_row(
+        func: &F,
+        row: &[Option<I::RefItem<'_>>],
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError>;
+}
+
+impl<I: Array, O: Array, F> VariadicMode<I, O, F> for Strict
+where
+    F: Fn(&[I::RefItem<'_>]) -> O::OwnedItem,
+{
+    fn eval_row(
+        func: &F,
+        row: &[Option<I::RefItem<'_>>],
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        match row.iter().copied().collect::<Option<Vec<_>>>() {
+            Some(row) => builder.push(Some(func(&row).as_scalar_ref())),
+            None => builder.push(None),
+        }
+        Ok(())
+    }
+}
+
+impl<I: Array, O: Array, F> VariadicMode<I, O, F> for NullAware
+where
+    F: Fn(&[Option<I::RefItem<'_>>]) -> Option<O::OwnedItem>,
+{
+    fn eval_row(
+        func: &F,
+        row: &[Option<I::RefItem<'_>>],
+        builder: &mut O::Builder,
+    ) -> Result<(), EvalError> {
+        builder.push(func(row).as_ref().map(Scalar::as_scalar_ref));
+        Ok(())
+    }
+}
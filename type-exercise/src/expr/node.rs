@@ -0,0 +1,318 @@
+use super::{Expression, FunctionError, FunctionRegistry, FunctionSignature};
+use crate::array::{ArrayBuilderImpl, ArrayImpl};
+use crate::chunk::DataChunk;
+use crate::datatype::DataType;
+use crate::scalar::ScalarImpl;
+use crate::{EvalError, TypeMismatch};
+
+/// A function overload resolved from a [`FunctionRegistry`], ready to be evaluated.
+pub struct BoundFunction {
+    signature: FunctionSignature,
+    expr: Box<dyn Expression>,
+}
+
+impl BoundFunction {
+    pub fn signature(&self) -> &FunctionSignature {
+        &self.signature
+    }
+}
+
+/// A node of an expression tree, evaluated against the columns of a [`DataChunk`].
+pub enum ExprNode {
+    /// The column at `index` of the input chunk.
+    InputRef { index: usize, data_type: DataType },
+    /// A constant value.
+    Literal(ScalarImpl),
+    /// A function applied to the results of its arguments.
+    FunctionCall {
+        func: BoundFunction,
+        args: Vec<ExprNode>,
+    },
+}
+
+impl ExprNode {
+    /// Reference the column at `index`, which must be of `data_type`.
+    pub fn input_ref(index: usize, data_type: DataType) -> Self {
+        Self::InputRef { index, data_type }
+    }
+
+    pub fn literal(value: impl Into<ScalarImpl>) -> Self {
+        Self::Literal(value.into())
+    }
+
+    /// Call function `name` on `args`, picking the overload that matches their return types.
+    pub fn function_call(
+        registry: &FunctionRegistry,
+        name: &str,
+        args: Vec<ExprNode>,
+    ) -> Result<Self, FunctionError> {
+        let arg_types: Vec<_> = args.iter().map(|arg| arg.return_type()).collect();
+        let func = registry.lookup(name, &arg_types)?;
+        Ok(Self::FunctionCall {
+            func: BoundFunction {
+                signature: func.signature().clone(),
+                expr: func.build(),
+            },
+            args,
+        })
+    }
+
+    /// The type of the array this node evaluates to.
+    pub fn return_type(&self) -> DataType {
+        match self {
+            Self::InputRef { data_type, .. } => *data_type,
+            Self::Literal(value) => value.data_type(),
+            Self::FunctionCall { func, .. } => func.signature.ret,
+        }
+    }
+
+    /// Evaluate the tree against `chunk`, returning an array with one item per row.
+    pub fn eval(&self, chunk: &DataChunk) -> Result<ArrayImpl, EvalError> {
+        match self {
+            Self::InputRef { index, data_type } => {
+                let column = chunk.column(*index).ok_or_else(|| {
+                    EvalError::InvalidArgument(format!("column index {index} is out of bounds"))
+                })?;
+                if column.data_type() != *data_type {
+                    return Err(TypeMismatch {
+                        expected: *data_type,
+                        actual: column.data_type(),
+                    }
+                    .into());
+                }
+                Ok(column.clone())
+            }
+            Self::Literal(value) => Self::broadcast(value, chunk.cardinality()),
+            Self::FunctionCall { func, args } => {
+                let inputs = args
+                    .iter()
+                    .map(|arg| match arg {
+                        Self::Literal(_) => Ok(None),
+                        arg => arg.eval(chunk).map(Some),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                // If the only non-literal argument is dictionary-encoded, the function runs once
+                // per dictionary entry instead of once per row.
+                let mut arrays = inputs.iter().flatten();
+                if let (Some(ArrayImpl::Dictionary(dict)), None) = (arrays.next(), arrays.next()) {
+                    return dict.map_entries(|entries| {
+                        let inputs: Vec<_> = inputs
+                            .iter()
+                            .map(|input| input.as_ref().map(|_| entries.clone()))
+                            .collect();
+                        Self::call(func, args, &inputs, entries.len())
+                    });
+                }
+                Self::call(func, args, &inputs, chunk.cardinality())
+            }
+        }
+    }
+
+    /// Build an array of `len` copies of `value`.
+    fn broadcast(value: &ScalarImpl, len: usize) -> Result<ArrayImpl, EvalError> {
+        let mut builder = ArrayBuilderImpl::with_capacity(value.data_type(), len);
+        for _ in 0..len {
+            builder.try_push(Some(value.as_scalar_ref()))?;
+        }
+        Ok(builder.finish())
+    }
+
+    /// Evaluate `func` on `inputs`, with the literals among `args` broadcast to `len` items in
+    /// place of the missing inputs.
+    fn call(
+        func: &BoundFunction,
+        args: &[ExprNode],
+        inputs: &[Option<ArrayImpl>],
+        len: usize,
+    ) -> Result<ArrayImpl, EvalError> {
+        let args = args
+            .iter()
+            .zip(inputs)
+            .map(|(arg, input)| match (arg, input) {
+                (_, Some(input)) => Ok(input.clone()),
+                (Self::Literal(value), None) => Self::broadcast(value, len),
+                _ => unreachable!("only literals are left unevaluated"),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let args: Vec<_> = args.iter().collect();
+        func.expr.eval_expr(&args)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::array::*;
+    use crate::chunk::{DataChunk, Field, Schema};
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::test_util::*;
+    use crate::EvalError;
+
+    fn chunk() -> DataChunk {
+        DataChunk::try_new(
+            Schema::new(vec![
+                Field::new("a", DataType::Int64, true),
+                Field::new("b", DataType::Int64, true),
+                Field::new("c", DataType::String, true),
+            ]),
+            vec![
+                I64Array::from_slice(&[Some(1), Some(5), None, Some(2)]).into(),
+                I64Array::from_slice(&[Some(2), Some(3), Some(3), Some(3)]).into(),
+                StringArray::from_slice(&[Some("x"), Some("y"), Some("xx"), None]).into(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_eval_function_call() {
+        let registry = FunctionRegistry::with_builtins();
+        let expr = ExprNode::function_call(
+            &registry,
+            "cmp_lt",
+            vec![
+                ExprNode::input_ref(0, DataType::Int64),
+                ExprNode::input_ref(1, DataType::Int64),
+            ],
+        )
+        .unwrap();
+        assert_eq!(expr.return_type(), DataType::Bool);
+        let result = expr.eval(&chunk()).unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(false), None, Some(true)],
+        );
+    }
+
+    #[test]
+    fn test_eval_nested_with_literals() {
+        let registry = FunctionRegistry::with_builtins();
+        // str_contains(str_concat(c, 'a'), 'xa')
+        let concat = ExprNode::function_call(
+            &registry,
+            "str_concat",
+            vec![
+                ExprNode::input_ref(2, DataType::String),
+                ExprNode::literal("a".to_string()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(concat.return_type(), DataType::String);
+        let expr = ExprNode::function_call(
+            &registry,
+            "str_contains",
+            vec![concat, ExprNode::literal("xa".to_string())],
+        )
+        .unwrap();
+        let result = expr.eval(&chunk()).unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(false), Some(true), None],
+        );
+    }
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counting_contains(i1: &str, i2: &str) -> bool {
+        CALLS.fetch_add(1, Ordering::Relaxed);
+        str_contains(i1, i2)
+    }
+
+    #[test]
+    fn test_eval_per_dictionary_entry() {
+        let mut registry = FunctionRegistry::with_builtins();
+        registry.register(
+            "counting_contains",
+            &[DataType::String, DataType::String],
+            DataType::Bool,
+            || {
+                Box::new(
+                    BinaryExpression::<StringArray, StringArray, BoolArray, _>::new(
+                        counting_contains,
+                    ),
+                )
+            },
+        );
+        let data = [
+            Some("de"),
+            Some("fr"),
+            None,
+            Some("de"),
+            Some("fr"),
+            Some("de"),
+        ];
+        let chunk = DataChunk::try_new(
+            Schema::new(vec![Field::new("a", DataType::String, true)]),
+            vec![data.into_iter().collect::<DictionaryArray>().into()],
+        )
+        .unwrap();
+        let call = |name| {
+            ExprNode::function_call(
+                &registry,
+                name,
+                vec![
+                    ExprNode::input_ref(0, DataType::String),
+                    ExprNode::literal("e".to_string()),
+                ],
+            )
+            .unwrap()
+            .eval(&chunk)
+            .unwrap()
+        };
+        let expected = [
+            Some(true),
+            Some(false),
+            None,
+            Some(true),
+            Some(false),
+            Some(true),
+        ];
+
+        // Two distinct values and a null over six rows: the kernel runs on the two values only.
+        let result = call("counting_contains");
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &expected);
+
+        let result = call("str_contains");
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &expected);
+        let result = call("cmp_lt");
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &data.map(|item| item.map(|item| item < "e")),
+        );
+    }
+
+    #[test]
+    fn test_function_call_type_error() {
+        let registry = FunctionRegistry::with_builtins();
+        let result = ExprNode::function_call(
+            &registry,
+            "str_contains",
+            vec![
+                ExprNode::input_ref(2, DataType::String),
+                ExprNode::literal(1i64),
+            ],
+        );
+        assert!(matches!(
+            result,
+            Err(FunctionError::NoMatchingOverload { .. })
+        ));
+    }
+
+    #[test]
+    fn test_input_ref_type_error() {
+        assert!(matches!(
+            ExprNode::input_ref(2, DataType::Int64).eval(&chunk()),
+            Err(EvalError::TypeMismatch {
+                expected: DataType::Int64,
+                actual: DataType::String
+            })
+        ));
+        assert!(matches!(
+            ExprNode::input_ref(3, DataType::Int64).eval(&chunk()),
+            Err(EvalError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use super::Expression;
+use crate::datatype::DataType;
+
+/// Creates the expression that evaluates one overload of a function.
+pub type ExpressionFactory = fn() -> Box<dyn Expression>;
+
+/// The signature of one overload of a function: `name(args...) -> ret`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSignature {
+    pub name: String,
+    pub args: Vec<DataType>,
+    /// Type of any number of extra arguments after `args`, if the function is variadic.
+    pub variadic: Option<DataType>,
+    pub ret: DataType,
+}
+
+impl FunctionSignature {
+    /// Check if this overload accepts arguments of exactly `args`.
+    pub fn matches(&self, args: &[DataType]) -> bool {
+        match self.variadic {
+            None => self.args == args,
+            Some(variadic) => {
+                args.len() >= self.args.len()
+                    && self.args == args[..self.args.len()]
+                    && args[self.args.len()..].iter().all(|arg| *arg == variadic)
+            }
+        }
+    }
+}
+
+/// A registered overload of a function.
+pub struct FunctionImpl {
+    signature: FunctionSignature,
+    factory: ExpressionFactory,
+}
+
+impl FunctionImpl {
+    /// The signature of this overload.
+    pub fn signature(&self) -> &FunctionSignature {
+        &self.signature
+    }
+
+    /// The return type of this overload.
+    pub fn return_type(&self) -> DataType {
+        self.signature.ret
+    }
+
+    /// Build a new expression that evaluates this overload.
+    pub fn build(&self) -> Box<dyn Expression> {
+        (self.factory)()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FunctionError {
+    #[error("function `{0}` does not exist")]
+    NotFound(String),
+    #[error("function `{name}` has no overload for argument types {args:?}")]
+    NoMatchingOverload { name: String, args: Vec<DataType> },
+}
+
+/// A collection of functions, each of which may have several overloads distinguished by their
+/// argument types.
+#[derive(Default)]
+pub struct FunctionRegistry {
+    functions: HashMap<String, Vec<FunctionImpl>>,
+}
+
+impl FunctionRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with all built-in functions registered.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        super::arithmetic::register_arithmetic_functions(&mut registry);
+        super::cmp::register_cmp_functions(&mut registry);
+        super::conditional::register_conditional_functions(&mut registry);
+        super::logical::register_logical_functions(&mut registry);
+        super::string::register_string_functions(&mut registry);
+        registry
+    }
+
+    /// Register an overload of function `name`. An existing overload with the same argument
+    /// types is replaced.
+    pub fn register(
+        &mut self,
+        name: &str,
+        args: &[DataType],
+        ret: DataType,
+        factory: ExpressionFactory,
+    ) {
+        self.register_signature(
+            FunctionSignature {
+                name: name.to_string(),
+                args: args.to_vec(),
+                variadic: None,
+                ret,
+            },
+            factory,
+        );
+    }
+
+    /// Register an overload of function `name` that takes `args` followed by any number of
+    /// arguments of type `variadic`. An existing overload with the same argument types is
+    /// replaced.
+    pub fn register_variadic(
+        &mut self,
+        name: &str,
+        args: &[DataType],
+        variadic: DataType,
+        ret: DataType,
+        factory: ExpressionFactory,
+    ) {
+        self.register_signature(
+            FunctionSignature {
+                name: name.to_string(),
+                args: args.to_vec(),
+                variadic: Some(variadic),
+                ret,
+            },
+            factory,
+        );
+    }
+
+    fn register_signature(&mut self, signature: FunctionSignature, factory: ExpressionFactory) {
+        let overloads = self.functions.entry(signature.name.clone()).or_default();
+        overloads.retain(|f| {
+            f.signature.args != signature.args || f.signature.variadic != signature.variadic
+        });
+        overloads.push(FunctionImpl { signature, factory });
+    }
+
+    /// Find the overload of function `name` that accepts exactly `args`. Non-variadic overloads
+    /// are preferred.
+    pub fn lookup(&self, name: &str, args: &[DataType]) -> Result<&FunctionImpl, FunctionError> {
+        let overloads = self
+            .functions
+            .get(name)
+            .ok_or_else(|| FunctionError::NotFound(name.to_string()))?;
+        overloads
+            .iter()
+            .find(|f| f.signature.variadic.is_none() && f.signature.matches(args))
+            .or_else(|| overloads.iter().find(|f| f.signature.matches(args)))
+            .ok_or_else(|| FunctionError::NoMatchingOverload {
+                name: name.to_string(),
+                args: args.to_vec(),
+            })
+    }
+
+    /// Build an expression for the overload of function `name` that accepts exactly `args`.
+    pub fn build(
+        &self,
+        name: &str,
+        args: &[DataType],
+    ) -> Result<Box<dyn Expression>, FunctionError> {
+        Ok(self.lookup(name, args)?.build())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::test_util::*;
+
+    #[test]
+    fn test_cmp_on_all_types() {
+        let registry = FunctionRegistry::with_builtins();
+        for ty in [
+            DataType::Int32,
+            DataType::Int64,
+            DataType::Float64,
+            DataType::Bool,
+            DataType::String,
+        ] {
+            for name in ["cmp_lt", "cmp_le", "cmp_gt", "cmp_ge", "cmp_eq", "cmp_ne"] {
+                let func = registry.lookup(name, &[ty, ty]).unwrap();
+                assert_eq!(func.return_type(), DataType::Bool);
+            }
+        }
+    }
+
+    #[test]
+    fn test_str_cmp_lt() {
+        let registry = FunctionRegistry::with_builtins();
+        let expr = registry
+            .build("cmp_lt", &[DataType::String, DataType::String])
+            .unwrap();
+        let result = expr
+            .eval_expr(&[
+                &StringArray::from_slice(&[Some("aa"), Some("bb"), None]).into(),
+                &StringArray::from_slice(&[Some("ab"), Some("ba"), Some("cc")]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(false), None],
+        );
+    }
+
+    #[test]
+    fn test_str_concat() {
+        let registry = FunctionRegistry::with_builtins();
+        let func = registry
+            .lookup("str_concat", &[DataType::String, DataType::String])
+            .unwrap();
+        assert_eq!(func.return_type(), DataType::String);
+        let result = func
+            .build()
+            .eval_expr(&[
+                &StringArray::from_slice(&[Some("aa"), Some("bb"), None]).into(),
+                &StringArray::from_slice(&[Some("cc"), None, Some("dd")]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<StringArray>((&result).try_into().unwrap(), &[Some("aacc"), None, None]);
+    }
+
+    #[test]
+    fn test_lookup_errors() {
+        let registry = FunctionRegistry::with_builtins();
+        assert!(matches!(
+            registry.lookup("no_such_function", &[DataType::Int32]),
+            Err(FunctionError::NotFound(name)) if name == "no_such_function"
+        ));
+        assert!(matches!(
+            registry.lookup("str_contains", &[DataType::String, DataType::Int32]),
+            Err(FunctionError::NoMatchingOverload { name, args })
+                if name == "str_contains" && args == [DataType::String, DataType::Int32]
+        ));
+    }
+
+    #[test]
+    fn test_register_replaces_overload() {
+        let mut registry = FunctionRegistry::new();
+        let args = [DataType::String, DataType::String];
+        registry.register("f", &args, DataType::Bool, || {
+            Box::new(BinaryExpression::<StringArray, StringArray, BoolArray, _>::new(str_contains))
+        });
+        registry.register("f", &args, DataType::String, || {
+            Box::new(BinaryExpression::<
+                StringArray,
+                StringArray,
+                StringArray,
+                _,
+                _,
+            >::new_writer(str_concat))
+        });
+        assert_eq!(
+            registry.lookup("f", &args).unwrap().return_type(),
+            DataType::String
+        );
+    }
+
+    #[test]
+    fn test_lookup_variadic() {
+        let registry = FunctionRegistry::with_builtins();
+        let string = DataType::String;
+        let func = registry
+            .lookup("concat_ws", &[string, string, string, string])
+            .unwrap();
+        assert_eq!(func.signature().variadic, Some(string));
+        assert!(registry.lookup("concat_ws", &[string]).is_ok());
+        assert!(matches!(
+            registry.lookup("concat_ws", &[string, DataType::Int32]),
+            Err(FunctionError::NoMatchingOverload { .. })
+        ));
+        assert!(matches!(
+            registry.lookup("coalesce", &[]),
+            Err(FunctionError::NoMatchingOverload { .. })
+        ));
+        assert_eq!(
+            registry
+                .lookup("coalesce", &[DataType::Int64; 3])
+                .unwrap()
+                .return_type(),
+            DataType::Int64
+        );
+    }
+}
@@ -1,9 +1,145 @@
+use super::{
+    BinaryExpression, FunctionRegistry, TernaryExpression, UnaryExpression, VariadicExpression,
+};
+use crate::array::{BoolArray, I32Array, StringArray, StringWriter};
+use crate::datatype::DataType;
+
 pub fn str_contains(i1: &str, i2: &str) -> bool {
     i1.contains(i2)
 }
 
-pub fn str_concat(i1: &str, i2: &str) -> String {
-    i1.to_owned() + i2
+pub fn str_concat(i1: &str, i2: &str, writer: &mut StringWriter<'_>) {
+    writer.push_str(i1);
+    writer.push_str(i2);
+}
+
+/// Number of characters in the string.
+pub fn str_length(i: &str) -> i32 {
+    i.chars().count() as i32
+}
+
+pub fn str_upper(i: &str, writer: &mut StringWriter<'_>) {
+    i.chars()
+        .flat_map(char::to_uppercase)
+        .for_each(|c| writer.push(c));
+}
+
+pub fn str_lower(i: &str, writer: &mut StringWriter<'_>) {
+    // A capital sigma lowers depending on its position in the word, which only
+    // `str::to_lowercase` knows about.
+    if i.contains('Σ') {
+        writer.push_str(&i.to_lowercase());
+        return;
+    }
+    i.chars()
+        .flat_map(char::to_lowercase)
+        .for_each(|c| writer.push(c));
+}
+
+/// Replace all occurrences of `from` in `i` with `to`.
+pub fn str_replace(i: &str, from: &str, to: &str, writer: &mut StringWriter<'_>) {
+    if from.is_empty() {
+        // Matches `str::replace`, which inserts `to` around every character.
+        writer.push_str(to);
+        for c in i.chars() {
+            writer.push(c);
+            writer.push_str(to);
+        }
+        return;
+    }
+    let mut last = 0;
+    for (start, part) in i.match_indices(from) {
+        writer.push_str(&i[last..start]);
+        writer.push_str(to);
+        last = start + part.len();
+    }
+    writer.push_str(&i[last..]);
+}
+
+/// The `len` characters starting at the 1-based character position `start`. Positions before the
+/// first character count towards `len` but select nothing, as in SQL.
+pub fn str_substr(i: &str, start: i32, len: i32, writer: &mut StringWriter<'_>) {
+    let end = (start as i64 + len as i64).max(1);
+    let start = start.max(1) as i64;
+    let skip = (start - 1) as usize;
+    let take = (end - start).max(0) as usize;
+    let begin = i.char_indices().nth(skip).map_or(i.len(), |(idx, _)| idx);
+    let rest = &i[begin..];
+    let end = rest
+        .char_indices()
+        .nth(take)
+        .map_or(rest.len(), |(idx, _)| idx);
+    writer.push_str(&rest[..end]);
+}
+
+/// Join the non-null arguments after the first one with the first one as separator. Returns
+/// null if the separator is null.
+pub fn str_concat_ws(args: &[Option<&str>]) -> Option<String> {
+    let (sep, args) = args.split_first()?;
+    let sep = (*sep)?;
+    Some(args.iter().flatten().copied().collect::<Vec<_>>().join(sep))
+}
+
+/// Register functions on strings.
+pub(super) fn register_string_functions(registry: &mut FunctionRegistry) {
+    let args = [DataType::String, DataType::String];
+    registry.register("str_contains", &args, DataType::Bool, || {
+        Box::new(BinaryExpression::<StringArray, StringArray, BoolArray, _>::new(str_contains))
+    });
+    registry.register("str_concat", &args, DataType::String, || {
+        Box::new(BinaryExpression::<
+            StringArray,
+            StringArray,
+            StringArray,
+            _,
+            _,
+        >::new_writer(str_concat))
+    });
+
+    let args = [DataType::String];
+    registry.register("length", &args, DataType::Int32, || {
+        Box::new(UnaryExpression::<StringArray, I32Array, _>::new(str_length))
+    });
+    registry.register("upper", &args, DataType::String, || {
+        Box::new(UnaryExpression::<StringArray, StringArray, _, _>::new_writer(str_upper))
+    });
+    registry.register("lower", &args, DataType::String, || {
+        Box::new(UnaryExpression::<StringArray, StringArray, _, _>::new_writer(str_lower))
+    });
+
+    let args = [DataType::String; 3];
+    registry.register("replace", &args, DataType::String, || {
+        Box::new(TernaryExpression::<
+            StringArray,
+            StringArray,
+            StringArray,
+            StringArray,
+            _,
+            _,
+        >::new_writer(str_replace))
+    });
+    let args = [DataType::String, DataType::Int32, DataType::Int32];
+    registry.register("substr", &args, DataType::String, || {
+        Box::new(TernaryExpression::<
+            StringArray,
+            I32Array,
+            I32Array,
+            StringArray,
+            _,
+            _,
+        >::new_writer(str_substr))
+    });
+    registry.register_variadic(
+        "concat_ws",
+        &[DataType::String],
+        DataType::String,
+        DataType::String,
+        || {
+            Box::new(
+                VariadicExpression::<StringArray, StringArray, _, _>::new_null_aware(str_concat_ws),
+            )
+        },
+    );
 }
 
 #[cfg(test)]
@@ -29,7 +165,8 @@ mod test {
 
     #[test]
     fn test_concat_string() {
-        let expr = BinaryExpression::<StringArray, StringArray, StringArray, _>::new(str_concat);
+        let expr =
+            BinaryExpression::<StringArray, StringArray, StringArray, _, _>::new_writer(str_concat);
         let result = expr
             .eval(
                 &StringArray::from_slice(&[Some("aa"), Some("bb"), None]).into(),
@@ -38,4 +175,40 @@ mod test {
             .unwrap();
         check_array_eq::<StringArray>((&result).try_into().unwrap(), &[Some("aaaa"), None, None]);
     }
+
+    /// Run a writer-based kernel on one row.
+    fn write(f: impl FnOnce(&mut StringWriter<'_>)) -> String {
+        let mut builder = StringArrayBuilder::with_capacity(1);
+        builder.push_with(f);
+        builder.finish().get(0).unwrap().to_owned()
+    }
+
+    #[test]
+    fn test_str_substr() {
+        assert_eq!(write(|w| str_substr("hello", 2, 3, w)), "ell");
+        assert_eq!(write(|w| str_substr("hello", 4, 10, w)), "lo");
+        assert_eq!(write(|w| str_substr("hello", -1, 3, w)), "h");
+        assert_eq!(write(|w| str_substr("hello", 1, -1, w)), "");
+        assert_eq!(write(|w| str_substr("hello", 9, 1, w)), "");
+        assert_eq!(write(|w| str_substr("日本語", 2, 1, w)), "本");
+    }
+
+    #[test]
+    fn test_str_writer_kernels() {
+        assert_eq!(write(|w| str_concat("ab", "cd", w)), "abcd");
+        assert_eq!(write(|w| str_upper("straße", w)), "STRASSE");
+        assert_eq!(write(|w| str_lower("ÀB", w)), "àb");
+        assert_eq!(write(|w| str_lower("ΟΔΟΣ", w)), "οδος");
+        for i in ["Σ", "ΣΑ ΟΔΟΣ.", "aΣb"] {
+            assert_eq!(write(|w| str_lower(i, w)), i.to_lowercase());
+        }
+        for (i, from, to) in [
+            ("aXbXc", "X", "--"),
+            ("aaa", "aa", "b"),
+            ("ab", "", "-"),
+            ("", "x", "y"),
+        ] {
+            assert_eq!(write(|w| str_replace(i, from, to, w)), i.replace(from, to));
+        }
+    }
 }
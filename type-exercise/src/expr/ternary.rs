@@ -0,0 +1,210 @@
+use super::{Expression, NullAware, Strict, TernaryMode, Writer};
+use crate::array::{Array, ArrayBuilder, ArrayImpl, StringArray};
+use crate::{EvalError, TypeMismatch};
+
+/// An expression of three arguments. `N` picks the null semantics, see [`Strict`],
+/// [`NullAware`] and [`Writer`].
+pub struct TernaryExpression<I1, I2, I3, O, F, N = Strict> {
+    func: F,
+    _phantom: std::marker::PhantomData<(I1, I2, I3, O, N)>,
+}
+
+impl<I1: Array, I2: Array, I3: Array, O: Array, F> TernaryExpression<I1, I2, I3, O, F> {
+    /// Create an expression that returns null if any argument is null.
+    pub fn new(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I1: Array, I2: Array, I3: Array, O: Array, F> TernaryExpression<I1, I2, I3, O, F, NullAware> {
+    /// Create an expression whose function is called on nullable arguments.
+    pub fn new_null_aware(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I1: Array, I2: Array, I3: Array, F> TernaryExpression<I1, I2, I3, StringArray, F, Writer> {
+    /// Create an expression whose function writes its string result through a
+    /// [`StringWriter`](crate::array::StringWriter).
+    pub fn new_writer(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I1: Array, I2: Array, I3: Array, O: Array, F, N> TernaryExpression<I1, I2, I3, O, F, N>
+where
+    N: TernaryMode<I1, I2, I3, O, F>,
+{
+    pub fn eval(
+        &self,
+        i1: &ArrayImpl,
+        i2: &ArrayImpl,
+        i3: &ArrayImpl,
+    ) -> Result<ArrayImpl, EvalError>
+    where
+        for<'a> &'a I1: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+        for<'a> &'a I2: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+        for<'a> &'a I3: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+        O: Into<ArrayImpl>,
+    {
+        let (i1, i2, i3) = (i1.decode(), i2.decode(), i3.decode());
+        let i1: &I1 = (&*i1).try_into()?;
+        let i2: &I2 = (&*i2).try_into()?;
+        let i3: &I3 = (&*i3).try_into()?;
+        Ok(self.eval_array(i1, i2, i3)?.into())
+    }
+
+    /// Evaluate the expression on three arrays of concrete types.
+    pub fn eval_array(&self, i1: &I1, i2: &I2, i3: &I3) -> Result<O, EvalError> {
+        for len in [i2.len(), i3.len()] {
+            if len != i1.len() {
+                return Err(EvalError::LengthMismatch {
+                    left: i1.len(),
+                    right: len,
+                });
+            }
+        }
+
+        let mut builder = O::Builder::with_capacity(i1.len());
+        for ((a, b), c) in i1.iter().zip(i2.iter()).zip(i3.iter()) {
+            N::eval_row(&self.func, a, b, c, &mut builder)?;
+        }
+
+        Ok(builder.finish())
+    }
+}
+
+impl<I1: Array, I2: Array, I3: Array, O: Array, F, N> Expression
+    for TernaryExpression<I1, I2, I3, O, F, N>
+where
+    for<'a> &'a I1: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    for<'a> &'a I2: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    for<'a> &'a I3: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    N: TernaryMode<I1, I2, I3, O, F>,
+    O: Into<ArrayImpl>,
+{
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl, EvalError> {
+        if data.len() != 3 {
+            return Err(EvalError::ArityMismatch {
+                expected: 3,
+                actual: data.len(),
+            });
+        }
+        self.eval(data[0], data[1], data[2])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::test_util::*;
+    use crate::EvalError;
+
+    #[test]
+    fn test_strict_between() {
+        let expr =
+            TernaryExpression::<I32Array, I32Array, I32Array, BoolArray, _>::new(between::<i32>);
+        let result = expr
+            .eval(
+                &I32Array::from_slice(&[Some(1), Some(5), None, Some(3)]).into(),
+                &I32Array::from_slice(&[Some(0), Some(0), Some(0), None]).into(),
+                &I32Array::from_slice(&[Some(3), Some(3), Some(3), Some(3)]).into(),
+            )
+            .unwrap();
+        check_array_eq::<BoolArray>(
+            (&result).try_into().unwrap(),
+            &[Some(true), Some(false), None, None],
+        );
+    }
+
+    #[test]
+    fn test_null_aware() {
+        // Replace nulls in the first argument with the second one, unless the third is set.
+        let expr =
+            TernaryExpression::<I32Array, I32Array, BoolArray, I32Array, _, _>::new_null_aware(
+                |a: Option<i32>, b: Option<i32>, c: Option<bool>| match c {
+                    Some(true) => None,
+                    _ => a.or(b),
+                },
+            );
+        let result = expr
+            .eval(
+                &I32Array::from_slice(&[Some(1), None, None, Some(4)]).into(),
+                &I32Array::from_slice(&[Some(9), Some(9), None, None]).into(),
+                &BoolArray::from_slice(&[None, Some(false), Some(false), Some(true)]).into(),
+            )
+            .unwrap();
+        check_array_eq::<I32Array>(
+            (&result).try_into().unwrap(),
+            &[Some(1), Some(9), None, None],
+        );
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let expr =
+            TernaryExpression::<StringArray, StringArray, StringArray, StringArray, _, _>::new_writer(
+                str_replace,
+            );
+        let strings: ArrayImpl = StringArray::from_slice(&[Some("a"), Some("b")]).into();
+        let short: ArrayImpl = StringArray::from_slice(&[Some("a")]).into();
+        let ints: ArrayImpl = I32Array::from_slice(&[Some(1), Some(2)]).into();
+        assert!(matches!(
+            expr.eval_expr(&[&strings, &strings]),
+            Err(EvalError::ArityMismatch {
+                expected: 3,
+                actual: 2
+            })
+        ));
+        assert!(matches!(
+            expr.eval(&strings, &strings, &short),
+            Err(EvalError::LengthMismatch { left: 2, right: 1 })
+        ));
+        assert!(matches!(
+            expr.eval(&strings, &ints, &strings),
+            Err(EvalError::TypeMismatch {
+                expected: DataType::String,
+                actual: DataType::Int32
+            })
+        ));
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = FunctionRegistry::with_builtins();
+        let result = registry
+            .build(
+                "substr",
+                &[DataType::String, DataType::Int32, DataType::Int32],
+            )
+            .unwrap()
+            .eval_expr(&[
+                &StringArray::from_slice(&[Some("héllo"), Some("abc"), Some("abc"), None]).into(),
+                &I32Array::from_slice(&[Some(2), Some(0), Some(3), Some(1)]).into(),
+                &I32Array::from_slice(&[Some(3), Some(2), Some(5), Some(1)]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<StringArray>(
+            (&result).try_into().unwrap(),
+            &[Some("éll"), Some("a"), Some("c"), None],
+        );
+
+        for ty in [DataType::Int64, DataType::String] {
+            assert_eq!(
+                registry.lookup("between", &[ty; 3]).unwrap().return_type(),
+                DataType::Bool
+            );
+        }
+    }
+}
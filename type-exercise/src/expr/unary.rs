@@ -0,0 +1,195 @@
+use super::{Checked, Expression, NullAware, NullOnError, Strict, UnaryMode, Writer};
+use crate::array::{Array, ArrayBuilder, ArrayImpl, StringArray};
+use crate::{EvalError, TypeMismatch};
+
+/// An expression of one argument. `N` picks the null and error semantics, see [`Strict`],
+/// [`NullAware`], [`Checked`], [`NullOnError`] and [`Writer`].
+pub struct UnaryExpression<I, O, F, N = Strict> {
+    func: F,
+    _phantom: std::marker::PhantomData<(I, O, N)>,
+}
+
+impl<I: Array, O: Array, F> UnaryExpression<I, O, F> {
+    /// Create an expression that returns null if the argument is null.
+    pub fn new(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Array, O: Array, F> UnaryExpression<I, O, F, NullAware> {
+    /// Create an expression whose function is called on nullable arguments.
+    pub fn new_null_aware(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Array, O: Array, F> UnaryExpression<I, O, F, Checked> {
+    /// Create an expression whose function may fail, which fails the evaluation.
+    pub fn new_checked(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Array, O: Array, F> UnaryExpression<I, O, F, NullOnError> {
+    /// Create an expression whose function may fail, which makes the result of that row null.
+    pub fn new_null_on_error(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Array, F> UnaryExpression<I, StringArray, F, Writer> {
+    /// Create an expression whose function writes its string result through a
+    /// [`StringWriter`](crate::array::StringWriter).
+    pub fn new_writer(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Array, O: Array, F, N: UnaryMode<I, O, F>> UnaryExpression<I, O, F, N> {
+    pub fn eval(&self, i: &ArrayImpl) -> Result<ArrayImpl, EvalError>
+    where
+        for<'a> &'a I: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+        O: Into<ArrayImpl>,
+    {
+        if let ArrayImpl::Dictionary(dict) = i {
+            return dict.map_entries(|i| self.eval(i));
+        }
+        let i: &I = i.try_into()?;
+        Ok(self.eval_array(i)?.into())
+    }
+
+    /// Evaluate the expression on an array of concrete type.
+    pub fn eval_array(&self, i: &I) -> Result<O, EvalError> {
+        let mut builder = O::Builder::with_capacity(i.len());
+        for a in i.iter() {
+            N::eval_row(&self.func, a, &mut builder)?;
+        }
+        Ok(builder.finish())
+    }
+}
+
+impl<I: Array, O: Array, F, N> Expression for UnaryExpression<I, O, F, N>
+where
+    for<'a> &'a I: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    N: UnaryMode<I, O, F>,
+    O: Into<ArrayImpl>,
+{
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl, EvalError> {
+        if data.len() != 1 {
+            return Err(EvalError::ArityMismatch {
+                expected: 1,
+                actual: data.len(),
+            });
+        }
+        self.eval(data[0])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::test_util::*;
+    use crate::EvalError;
+
+    #[test]
+    fn test_i32_abs() {
+        let expr = UnaryExpression::<I32Array, I32Array, _, _>::new_checked(abs::<i32>);
+        let result = expr
+            .eval(&I32Array::from_slice(&[Some(-1), None, Some(2), Some(0)]).into())
+            .unwrap();
+        check_array_eq::<I32Array>(
+            (&result).try_into().unwrap(),
+            &[Some(1), None, Some(2), Some(0)],
+        );
+    }
+
+    #[test]
+    fn test_f64_abs() {
+        let expr = UnaryExpression::<F64Array, F64Array, _, _>::new_checked(abs::<f64>);
+        let result = expr
+            .eval(&F64Array::from_slice(&[Some(-1.5), Some(2.5), None]).into())
+            .unwrap();
+        check_array_eq::<F64Array>((&result).try_into().unwrap(), &[Some(1.5), Some(2.5), None]);
+    }
+
+    #[test]
+    fn test_str_upper_and_length() {
+        let input: ArrayImpl =
+            StringArray::from_slice(&[Some("aB"), None, Some("ÿ"), Some("")]).into();
+
+        let expr = UnaryExpression::<StringArray, StringArray, _, _>::new_writer(str_upper);
+        let result = expr.eval(&input).unwrap();
+        check_array_eq::<StringArray>(
+            (&result).try_into().unwrap(),
+            &[Some("AB"), None, Some("Ÿ"), Some("")],
+        );
+
+        let expr = UnaryExpression::<StringArray, I32Array, _>::new(str_length);
+        let result = expr.eval(&input).unwrap();
+        check_array_eq::<I32Array>(
+            (&result).try_into().unwrap(),
+            &[Some(2), None, Some(1), Some(0)],
+        );
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let expr = UnaryExpression::<StringArray, StringArray, _, _>::new_writer(str_lower);
+        let input: ArrayImpl = I32Array::from_slice(&[Some(1)]).into();
+        assert!(matches!(
+            expr.eval(&input),
+            Err(EvalError::TypeMismatch {
+                expected: DataType::String,
+                actual: DataType::Int32
+            })
+        ));
+        assert!(matches!(
+            expr.eval_expr(&[&input, &input]),
+            Err(EvalError::ArityMismatch {
+                expected: 1,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = FunctionRegistry::with_builtins();
+        let func = registry.lookup("lower", &[DataType::String]).unwrap();
+        assert_eq!(func.return_type(), DataType::String);
+        let result = func
+            .build()
+            .eval_expr(&[&StringArray::from_slice(&[Some("Ab"), None]).into()])
+            .unwrap();
+        check_array_eq::<StringArray>((&result).try_into().unwrap(), &[Some("ab"), None]);
+
+        assert_eq!(
+            registry
+                .lookup("length", &[DataType::String])
+                .unwrap()
+                .return_type(),
+            DataType::Int32
+        );
+        for ty in [DataType::Int32, DataType::Int64, DataType::Float64] {
+            assert_eq!(registry.lookup("abs", &[ty]).unwrap().return_type(), ty);
+        }
+        assert!(registry.lookup("abs", &[DataType::String]).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use super::{Expression, NullAware, Strict, VariadicMode};
+use crate::array::{Array, ArrayBuilder, ArrayImpl};
+use crate::{EvalError, TypeMismatch};
+
+/// An expression of one or more arguments of the same type. `N` picks the null semantics, see
+/// [`Strict`] and [`NullAware`].
+pub struct VariadicExpression<I, O, F, N = Strict> {
+    func: F,
+    _phantom: std::marker::PhantomData<(I, O, N)>,
+}
+
+impl<I: Array, O: Array, F> VariadicExpression<I, O, F> {
+    /// Create an expression that returns null if any argument is null.
+    pub fn new(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Array, O: Array, F> VariadicExpression<I, O, F, NullAware> {
+    /// Create an expression whose function is called on nullable arguments.
+    pub fn new_null_aware(func: F) -> Self {
+        Self {
+            func,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Array, O: Array, F, N: VariadicMode<I, O, F>> VariadicExpression<I, O, F, N> {
+    pub fn eval(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl, EvalError>
+    where
+        for<'a> &'a I: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+        O: Into<ArrayImpl>,
+    {
+        let data = data.iter().map(|array| array.decode()).collect::<Vec<_>>();
+        let data = data
+            .iter()
+            .map(|array| (&**array).try_into())
+            .collect::<Result<Vec<&I>, _>>()?;
+        Ok(self.eval_array(&data)?.into())
+    }
+
+    /// Evaluate the expression on arrays of concrete type.
+    pub fn eval_array(&self, data: &[&I]) -> Result<O, EvalError> {
+        let Some(first) = data.first() else {
+            return Err(EvalError::ArityMismatch {
+                expected: 1,
+                actual: 0,
+            });
+        };
+        for array in data {
+            if array.len() != first.len() {
+                return Err(EvalError::LengthMismatch {
+                    left: first.len(),
+                    right: array.len(),
+                });
+            }
+        }
+
+        let mut builder = O::Builder::with_capacity(first.len());
+        let mut row = Vec::with_capacity(data.len());
+        for idx in 0..first.len() {
+            row.clear();
+            row.extend(data.iter().map(|array| array.get(idx)));
+            N::eval_row(&self.func, &row, &mut builder)?;
+        }
+
+        Ok(builder.finish())
+    }
+}
+
+impl<I: Array, O: Array, F, N> Expression for VariadicExpression<I, O, F, N>
+where
+    for<'a> &'a I: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
+    N: VariadicMode<I, O, F>,
+    O: Into<ArrayImpl>,
+{
+    fn eval_expr(&self, data: &[&ArrayImpl]) -> Result<ArrayImpl, EvalError> {
+        self.eval(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::*;
+    use crate::test_util::*;
+    use crate::EvalError;
+
+    #[test]
+    fn test_strict() {
+        let expr = VariadicExpression::<I64Array, I64Array, _>::new(|row: &[i64]| row.iter().sum());
+        let result = expr
+            .eval(&[
+                &I64Array::from_slice(&[Some(1), Some(2), None]).into(),
+                &I64Array::from_slice(&[Some(10), Some(20), Some(30)]).into(),
+                &I64Array::from_slice(&[Some(100), Some(200), Some(300)]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<I64Array>((&result).try_into().unwrap(), &[Some(111), Some(222), None]);
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let registry = FunctionRegistry::with_builtins();
+        let expr = registry.build("coalesce", &[DataType::String; 3]).unwrap();
+        let result = expr
+            .eval_expr(&[
+                &StringArray::from_slice(&[Some("a"), None, None, None]).into(),
+                &StringArray::from_slice(&[Some("b"), Some("b"), None, None]).into(),
+                &StringArray::from_slice(&[Some("c"), Some("c"), Some("c"), None]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<StringArray>(
+            (&result).try_into().unwrap(),
+            &[Some("a"), Some("b"), Some("c"), None],
+        );
+
+        let expr = registry.build("coalesce", &[DataType::Bool]).unwrap();
+        let result = expr
+            .eval_expr(&[&BoolArray::from_slice(&[Some(false), None]).into()])
+            .unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[Some(false), None]);
+    }
+
+    #[test]
+    fn test_concat_ws() {
+        let registry = FunctionRegistry::with_builtins();
+        let expr = registry.build("concat_ws", &[DataType::String; 3]).unwrap();
+        let result = expr
+            .eval_expr(&[
+                &StringArray::from_slice(&[Some(","), Some("-"), None, Some("")]).into(),
+                &StringArray::from_slice(&[Some("a"), None, Some("a"), Some("a")]).into(),
+                &StringArray::from_slice(&[Some("b"), Some("b"), Some("b"), Some("b")]).into(),
+            ])
+            .unwrap();
+        check_array_eq::<StringArray>(
+            (&result).try_into().unwrap(),
+            &[Some("a,b"), Some("b"), None, Some("ab")],
+        );
+
+        let expr = registry.build("concat_ws", &[DataType::String]).unwrap();
+        let result = expr
+            .eval_expr(&[&StringArray::from_slice(&[Some(","), None]).into()])
+            .unwrap();
+        check_array_eq::<StringArray>((&result).try_into().unwrap(), &[Some(""), None]);
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let expr =
+            VariadicExpression::<StringArray, StringArray, _, _>::new_null_aware(str_concat_ws);
+        let strings: ArrayImpl = StringArray::from_slice(&[Some("a"), Some("b")]).into();
+        let short: ArrayImpl = StringArray::from_slice(&[Some("a")]).into();
+        let ints: ArrayImpl = I32Array::from_slice(&[Some(1), Some(2)]).into();
+        assert!(matches!(
+            expr.eval_expr(&[]),
+            Err(EvalError::ArityMismatch {
+                expected: 1,
+                actual: 0
+            })
+        ));
+        assert!(matches!(
+            expr.eval_expr(&[&strings, &strings, &short]),
+            Err(EvalError::LengthMismatch { left: 2, right: 1 })
+        ));
+        assert!(matches!(
+            expr.eval_expr(&[&strings, &ints]),
+            Err(EvalError::TypeMismatch {
+                expected: DataType::String,
+                actual: DataType::Int32
+            })
+        ));
+    }
+}
@@ -1,40 +1,118 @@
-use array::{Array, ArrayImpl};
+use array::*;
+use datatype::DataType;
+use expr::{
+    cmp_eq, cmp_ge, cmp_gt, cmp_le, cmp_lt, cmp_ne, str_concat, str_contains, BinaryExpression,
+    ExpressionFunc, LogicalExpression,
+};
+use macros::for_all_types;
 
 pub mod array;
+pub mod chunk;
+pub mod datatype;
+mod error;
 pub mod expr;
 pub(crate) mod macros;
 pub mod scalar;
 
+pub use error::{EvalError, TypeMismatch};
+
+/// Error returned by [`eval_binary`] when a function has no kernel for the given argument types.
 #[derive(Debug, thiserror::Error)]
-#[error("type mismatch")]
-pub struct TypeMismatch;
-
-#[allow(unused_variables)]
-pub fn eval_binary<'a, I1: Array, I2: Array>(
-    i1: &'a ArrayImpl,
-    i2: &'a ArrayImpl,
-) -> Result<ArrayImpl, TypeMismatch>
-where
-    &'a I1: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
-    &'a I2: TryFrom<&'a ArrayImpl, Error = TypeMismatch>,
-{
-    let i1: &I1 = i1.try_into()?;
-    let i2: &I2 = i2.try_into()?;
-    todo!()
+#[error("function {func:?} does not support argument types ({left}, {right})")]
+pub struct UnsupportedTypes {
+    pub func: ExpressionFunc,
+    pub left: DataType,
+    pub right: DataType,
+}
+
+macro_rules! impl_eval_binary {
+    ($({ $Name:ident, $Variant:ident, $Array:ident, $ArrayBuilder:ident, $Owned:ty, $Ref:ty }),*) => {
+        /// Evaluate a binary function on two arrays, picking the concrete array types at runtime.
+        pub fn eval_binary(
+            func: ExpressionFunc,
+            i1: &ArrayImpl,
+            i2: &ArrayImpl,
+        ) -> Result<ArrayImpl, EvalError> {
+            use ExpressionFunc::*;
+
+            let (i1, i2) = (i1.decode(), i2.decode());
+            let result: ArrayImpl = match (func, &*i1, &*i2) {
+                $(
+                    (CmpLt, ArrayImpl::$Variant(a), ArrayImpl::$Variant(b)) => {
+                        BinaryExpression::<$Array, $Array, BoolArray, _>::new(cmp_lt::<$Owned, $Owned, $Owned>)
+                            .eval_array(a, b)?
+                            .into()
+                    }
+                    (CmpLe, ArrayImpl::$Variant(a), ArrayImpl::$Variant(b)) => {
+                        BinaryExpression::<$Array, $Array, BoolArray, _>::new(cmp_le::<$Owned, $Owned, $Owned>)
+                            .eval_array(a, b)?
+                            .into()
+                    }
+                    (CmpGt, ArrayImpl::$Variant(a), ArrayImpl::$Variant(b)) => {
+                        BinaryExpression::<$Array, $Array, BoolArray, _>::new(cmp_gt::<$Owned, $Owned, $Owned>)
+                            .eval_array(a, b)?
+                            .into()
+                    }
+                    (CmpGe, ArrayImpl::$Variant(a), ArrayImpl::$Variant(b)) => {
+                        BinaryExpression::<$Array, $Array, BoolArray, _>::new(cmp_ge::<$Owned, $Owned, $Owned>)
+                            .eval_array(a, b)?
+                            .into()
+                    }
+                    (CmpEq, ArrayImpl::$Variant(a), ArrayImpl::$Variant(b)) => {
+                        BinaryExpression::<$Array, $Array, BoolArray, _>::new(cmp_eq::<$Owned, $Owned, $Owned>)
+                            .eval_array(a, b)?
+                            .into()
+                    }
+                    (CmpNe, ArrayImpl::$Variant(a), ArrayImpl::$Variant(b)) => {
+                        BinaryExpression::<$Array, $Array, BoolArray, _>::new(cmp_ne::<$Owned, $Owned, $Owned>)
+                            .eval_array(a, b)?
+                            .into()
+                    }
+                )*
+                (StrContains, ArrayImpl::String(a), ArrayImpl::String(b)) => {
+                    BinaryExpression::<StringArray, StringArray, BoolArray, _>::new(str_contains)
+                        .eval_array(a, b)?
+                        .into()
+                }
+                (StrConcat, ArrayImpl::String(a), ArrayImpl::String(b)) => {
+                    BinaryExpression::<StringArray, StringArray, StringArray, _, _>::new_writer(str_concat)
+                        .eval_array(a, b)?
+                        .into()
+                }
+                (And, ArrayImpl::Bool(a), ArrayImpl::Bool(b)) => {
+                    LogicalExpression::and().eval_array(a, b)?.into()
+                }
+                (Or, ArrayImpl::Bool(a), ArrayImpl::Bool(b)) => {
+                    LogicalExpression::or().eval_array(a, b)?.into()
+                }
+                (Xor, ArrayImpl::Bool(a), ArrayImpl::Bool(b)) => {
+                    LogicalExpression::xor().eval_array(a, b)?.into()
+                }
+                _ => {
+                    return Err(UnsupportedTypes {
+                        func,
+                        left: i1.data_type(),
+                        right: i2.data_type(),
+                    }
+                    .into())
+                }
+            };
+            Ok(result)
+        }
+    };
 }
 
+for_all_types! { impl_eval_binary }
+
 #[cfg(test)]
 #[allow(unused)]
 pub(crate) mod test_util {
-    use crate::array::{Array, ArrayBuilder};
+    use crate::array::{Array, ArrayImpl};
+    use crate::expr::FunctionRegistry;
 
     /// Build an array from a vector of data
     pub(crate) fn build_array_from_vec<A: Array>(items: &[Option<A::RefItem<'_>>]) -> A {
-        let mut builder = A::Builder::with_capacity(items.len());
-        for item in items {
-            builder.push(*item);
-        }
-        builder.finish()
+        A::from_items(items.iter().copied())
     }
 
     /// Test if an array has the same content as a vector
@@ -42,8 +120,120 @@ pub(crate) mod test_util {
     where
         A::RefItem<'a>: PartialEq,
     {
+        assert_eq!(array.len(), vec.len());
         for (a, b) in array.iter().zip(vec.iter()) {
             assert_eq!(&a, b);
         }
     }
+
+    /// Evaluate the built-in binary function `name` on every pair of `values`, returning the
+    /// results in row-major order.
+    pub(crate) fn eval_on_all_pairs<A: Array + Into<ArrayImpl>>(
+        name: &str,
+        values: &[Option<A::RefItem<'_>>],
+    ) -> ArrayImpl {
+        let (left, right): (Vec<_>, Vec<_>) = values
+            .iter()
+            .flat_map(|a| values.iter().map(move |b| (*a, *b)))
+            .unzip();
+        let left: ArrayImpl = A::from_slice(&left).into();
+        let right: ArrayImpl = A::from_slice(&right).into();
+        FunctionRegistry::with_builtins()
+            .build(name, &[left.data_type(), right.data_type()])
+            .unwrap()
+            .eval_expr(&[&left, &right])
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array::*;
+    use crate::datatype::DataType;
+    use crate::expr::ExpressionFunc;
+    use crate::scalar::ScalarRefImpl;
+    use crate::test_util::*;
+    use crate::{eval_binary, EvalError};
+
+    /// One array of each type in `for_all_types`, all of length 3.
+    fn arrays_of_all_types() -> Vec<ArrayImpl> {
+        vec![
+            I32Array::from_slice(&[Some(1), Some(2), Some(3)]).into(),
+            I64Array::from_slice(&[Some(1), Some(2), Some(3)]).into(),
+            F64Array::from_slice(&[Some(1.0), Some(2.0), Some(3.0)]).into(),
+            BoolArray::from_slice(&[Some(false), Some(true), Some(true)]).into(),
+            StringArray::from_slice(&[Some("a"), Some("b"), Some("c")]).into(),
+        ]
+    }
+
+    /// The output type of `func` on `(left, right)`, or `None` if there is no such signature.
+    fn expected_output(func: ExpressionFunc, left: DataType, right: DataType) -> Option<DataType> {
+        use ExpressionFunc::*;
+        match (func, left, right) {
+            (CmpLt | CmpLe | CmpGt | CmpGe | CmpEq | CmpNe, _, _) if left == right => {
+                Some(DataType::Bool)
+            }
+            (StrContains, DataType::String, DataType::String) => Some(DataType::Bool),
+            (StrConcat, DataType::String, DataType::String) => Some(DataType::String),
+            (And | Or | Xor, DataType::Bool, DataType::Bool) => Some(DataType::Bool),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_eval_binary_all_type_pairs() {
+        for func in ExpressionFunc::ALL {
+            for i1 in &arrays_of_all_types() {
+                for i2 in &arrays_of_all_types() {
+                    let result = eval_binary(func, i1, i2);
+                    match expected_output(func, i1.data_type(), i2.data_type()) {
+                        Some(output) => {
+                            let result = result.unwrap();
+                            assert_eq!(
+                                result.data_type(),
+                                output,
+                                "{func:?}({}, {})",
+                                i1.data_type(),
+                                i2.data_type()
+                            );
+                            assert_eq!(result.len(), 3);
+                        }
+                        None => {
+                            let Err(EvalError::UnsupportedTypes(err)) = result else {
+                                panic!(
+                                    "{func:?} should fail on ({}, {})",
+                                    i1.data_type(),
+                                    i2.data_type()
+                                );
+                            };
+                            assert_eq!(err.func, func);
+                            assert_eq!(err.left, i1.data_type());
+                            assert_eq!(err.right, i2.data_type());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_eval_binary_values() {
+        let result = eval_binary(
+            ExpressionFunc::CmpLt,
+            &I64Array::from_slice(&[Some(1), Some(3)]).into(),
+            &I64Array::from_slice(&[Some(2), Some(2)]).into(),
+        )
+        .unwrap();
+        check_array_eq::<BoolArray>((&result).try_into().unwrap(), &[Some(true), Some(false)]);
+
+        let result = eval_binary(
+            ExpressionFunc::StrConcat,
+            &StringArray::from_slice(&[Some("aa"), Some("bb"), None]).into(),
+            &StringArray::from_slice(&[Some("cc"), None, Some("dd")]).into(),
+        )
+        .unwrap();
+        assert_eq!(result.get(0), Some(ScalarRefImpl::String("aacc")));
+        assert!(result.get(1).is_none());
+        assert!(result.get(2).is_none());
+    }
 }
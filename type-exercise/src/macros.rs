@@ -19,8 +19,7 @@ macro_rules! for_all_primitive_types {
         $cb! {
             { Int32, I32, I32Array, I32ArrayBuilder, i32, i32 },
             { Int64, I64, I64Array, I64ArrayBuilder, i64, i64 },
-            { Float64, F64, F64Array, F64ArrayBuilder, f64, f64 },
-            { Bool, Bool, BoolArray, BoolArrayBuilder, bool, bool }
+            { Float64, F64, F64Array, F64ArrayBuilder, f64, f64 }
         }
     };
 }
@@ -36,3 +35,49 @@ macro_rules! for_all_composite_types {
 }
 
 pub(crate) use for_all_composite_types;
+
+/// Every pair of numeric types, with the type both are widened to when combined.
+///
+/// Each entry is `{ left, right, common }`, where each type is `Name, Array, Owned`. Any extra
+/// tokens after `$cb` are passed to it before the entries.
+macro_rules! for_all_numeric_type_pairs {
+    ($cb:path $(, $extra:tt)*) => {
+        $cb! {
+            $($extra,)*
+            { Int32, I32Array, i32, Int32, I32Array, i32, Int32, I32Array, i32 },
+            { Int32, I32Array, i32, Int64, I64Array, i64, Int64, I64Array, i64 },
+            { Int32, I32Array, i32, Float64, F64Array, f64, Float64, F64Array, f64 },
+            { Int64, I64Array, i64, Int32, I32Array, i32, Int64, I64Array, i64 },
+            { Int64, I64Array, i64, Int64, I64Array, i64, Int64, I64Array, i64 },
+            { Int64, I64Array, i64, Float64, F64Array, f64, Float64, F64Array, f64 },
+            { Float64, F64Array, f64, Int32, I32Array, i32, Float64, F64Array, f64 },
+            { Float64, F64Array, f64, Int64, I64Array, i64, Float64, F64Array, f64 },
+            { Float64, F64Array, f64, Float64, F64Array, f64, Float64, F64Array, f64 }
+        }
+    };
+}
+
+pub(crate) use for_all_numeric_type_pairs;
+
+/// Every pair of types that can be compared with each other, with the type both are converted
+/// to before comparing. Numeric types widen as in `for_all_numeric_type_pairs`, other types are
+/// only comparable with themselves.
+macro_rules! for_all_comparable_type_pairs {
+    ($cb:ident) => {
+        $crate::macros::for_all_numeric_type_pairs! { $crate::macros::with_non_numeric_type_pairs, $cb }
+    };
+}
+
+/// Call `$cb` with the numeric type pairs followed by the non-numeric comparable ones.
+macro_rules! with_non_numeric_type_pairs {
+    ($cb:ident, $($numeric:tt),*) => {
+        $cb! {
+            $($numeric,)*
+            { Bool, BoolArray, bool, Bool, BoolArray, bool, Bool, BoolArray, bool },
+            { String, StringArray, String, String, StringArray, String, String, StringArray, String }
+        }
+    };
+}
+
+pub(crate) use for_all_comparable_type_pairs;
+pub(crate) use with_non_numeric_type_pairs;
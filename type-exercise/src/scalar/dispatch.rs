@@ -1,4 +1,6 @@
+use crate::datatype::DataType;
 use crate::macros::for_all_types;
+use crate::scalar::Scalar;
 use crate::TypeMismatch;
 
 macro_rules! define_scalar_impl {
@@ -9,6 +11,26 @@ macro_rules! define_scalar_impl {
                 $Variant($Owned),
             )*
         }
+
+        impl ScalarImpl {
+            /// Get the data type of the scalar.
+            pub fn data_type(&self) -> DataType {
+                match self {
+                    $(
+                        Self::$Variant(_) => DataType::$Name,
+                    )*
+                }
+            }
+
+            /// Get a reference to the scalar.
+            pub fn as_scalar_ref(&self) -> ScalarRefImpl<'_> {
+                match self {
+                    $(
+                        Self::$Variant(this) => ScalarRefImpl::$Variant(this.as_scalar_ref()),
+                    )*
+                }
+            }
+        }
     };
 }
 
@@ -22,6 +44,17 @@ macro_rules! define_scalar_ref_impl {
                 $Variant($Ref),
             )*
         }
+
+        impl ScalarRefImpl<'_> {
+            /// Get the data type of the scalar.
+            pub fn data_type(&self) -> DataType {
+                match self {
+                    $(
+                        Self::$Variant(_) => DataType::$Name,
+                    )*
+                }
+            }
+        }
     };
 }
 
@@ -41,7 +74,10 @@ macro_rules! impl_scalar_conversion {
                 fn try_from(value: ScalarImpl) -> Result<Self, Self::Error> {
                     match value {
                         ScalarImpl::$Variant(this) => Ok(this),
-                        _ => Err(TypeMismatch),
+                        other => Err(TypeMismatch {
+                            expected: DataType::$Name,
+                            actual: other.data_type(),
+                        }),
                     }
                 }
             }
@@ -57,7 +93,10 @@ macro_rules! impl_scalar_conversion {
                 fn try_from(value: ScalarRefImpl<'a>) -> Result<Self, Self::Error> {
                     match value {
                         ScalarRefImpl::$Variant(this) => Ok(this),
-                        _ => Err(TypeMismatch),
+                        other => Err(TypeMismatch {
+                            expected: DataType::$Name,
+                            actual: other.data_type(),
+                        }),
                     }
                 }
             }
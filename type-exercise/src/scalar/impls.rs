@@ -2,7 +2,39 @@ use crate::array::*;
 use crate::macros::for_all_primitive_types;
 use crate::scalar::*;
 
-pub trait PrimitiveType: Copy + Send + Sync + std::fmt::Debug + 'static {}
+pub trait PrimitiveType: Copy + Default + Send + Sync + std::fmt::Debug + 'static {}
+
+/// Lossless (or, for `i64` to `f64`, nearest) conversion of a value into a wider type, used to
+/// combine values of different types.
+///
+/// Unlike [`Into`], this also converts `i64` into `f64`.
+pub trait Widen<T> {
+    fn widen(self) -> T;
+}
+
+impl<T> Widen<T> for T {
+    fn widen(self) -> T {
+        self
+    }
+}
+
+impl Widen<i64> for i32 {
+    fn widen(self) -> i64 {
+        self.into()
+    }
+}
+
+impl Widen<f64> for i32 {
+    fn widen(self) -> f64 {
+        self.into()
+    }
+}
+
+impl Widen<f64> for i64 {
+    fn widen(self) -> f64 {
+        self as f64
+    }
+}
 
 impl<T: PrimitiveType> Scalar for T {
     type ArrayType = PrimitiveArray<T>;
@@ -35,6 +67,25 @@ macro_rules! impl_scalar_for_primitive_types {
 
 for_all_primitive_types! { impl_scalar_for_primitive_types }
 
+impl Scalar for bool {
+    type ArrayType = BoolArray;
+    type RefType<'a> = bool;
+    fn as_scalar_ref(&self) -> Self::RefType<'_> {
+        *self
+    }
+    fn upcast_ref<'short, 'long: 'short>(r: Self::RefType<'long>) -> Self::RefType<'short> {
+        r
+    }
+}
+
+impl ScalarRef<'_> for bool {
+    type ArrayType = BoolArray;
+    type ScalarType = bool;
+    fn to_owned_scalar(&self) -> Self::ScalarType {
+        *self
+    }
+}
+
 impl Scalar for String {
     type ArrayType = StringArray;
     type RefType<'a> = &'a str;